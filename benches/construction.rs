@@ -0,0 +1,43 @@
+// benchmarks are not built with the MSRV toolchain
+#![allow(clippy::incompatible_msrv)]
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use entity_tag::{EntityTag, EntityTagList, EntityTagRef};
+
+const QUOTED: &str = "\"5f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f\"";
+const UNQUOTED: &str = "5f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f";
+const WEAK: &str = "W/\"5f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f\"";
+const LIST: &str = "\"a1b2c3\", W/\"d4e5f6\", \"5f3c2a1b9e8d7c6b5a4f3e2d1c0b9a8f\"";
+
+fn construction(c: &mut Criterion) {
+    c.bench_function("with_str quoted", |b| {
+        b.iter(|| EntityTag::with_str(false, black_box(QUOTED)).unwrap())
+    });
+    c.bench_function("with_str unquoted", |b| {
+        b.iter(|| EntityTag::with_str(false, black_box(UNQUOTED)).unwrap())
+    });
+    c.bench_function("with_string quoted", |b| {
+        b.iter(|| EntityTag::with_string(false, black_box(QUOTED).to_string()).unwrap())
+    });
+    c.bench_function("with_string unquoted", |b| {
+        b.iter(|| EntityTag::with_string(false, black_box(UNQUOTED).to_string()).unwrap())
+    });
+}
+
+fn parsing(c: &mut Criterion) {
+    c.bench_function("EntityTagRef::from_str", |b| {
+        b.iter(|| EntityTagRef::from_str(black_box(WEAK)).unwrap())
+    });
+    c.bench_function("from_str", |b| b.iter(|| EntityTag::from_str(black_box(WEAK)).unwrap()));
+    c.bench_function("from_string", |b| {
+        b.iter(|| EntityTag::from_string(black_box(WEAK).to_string()).unwrap())
+    });
+    c.bench_function("EntityTagList::from_str", |b| {
+        b.iter(|| EntityTagList::from_str(black_box(LIST)).unwrap())
+    });
+}
+
+criterion_group!(benches, construction, parsing);
+criterion_main!(benches);
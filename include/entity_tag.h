@@ -0,0 +1,29 @@
+#ifndef ENTITY_TAG_H
+#define ENTITY_TAG_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct EntityTagHandle EntityTagHandle;
+
+EntityTagHandle *entity_tag_new(bool weak, const uint8_t *tag, size_t len);
+EntityTagHandle *entity_tag_parse(const uint8_t *s, size_t len);
+EntityTagHandle *entity_tag_from_data(const uint8_t *data, size_t len);
+void entity_tag_free(EntityTagHandle *etag);
+
+bool entity_tag_is_weak(const EntityTagHandle *etag);
+bool entity_tag_strong_eq(const EntityTagHandle *a, const EntityTagHandle *b);
+bool entity_tag_weak_eq(const EntityTagHandle *a, const EntityTagHandle *b);
+
+size_t entity_tag_write(const EntityTagHandle *etag, uint8_t *buf, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,34 @@
+use std::{borrow::Cow, fs::Metadata, time::UNIX_EPOCH};
+
+use crate::EntityTag;
+
+impl<'t> EntityTag<'t> {
+    /// Construct a strong EntityTag from the metadata of a file in the format generated by `actix_files::NamedFile`, such as `"2a4f1c:1f40:65a8c3e1:1dcd6500"`, so that a file streamed manually has the same validator as the one served by `actix-files`.
+    ///
+    /// The opaque tag consists of the inode number (`0` on non-Unix platforms), the length, and the seconds and the nanoseconds of the modified time, in lowercase hexadecimal digits separated by colons. Returns `None` if the modified time is unavailable or earlier than the Unix epoch, in which case `actix-files` does not send an entity tag.
+    pub fn from_file_meta_actix_files(metadata: &Metadata) -> Option<EntityTag<'static>> {
+        let time = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+
+        #[cfg(unix)]
+        let ino = {
+            use std::os::unix::fs::MetadataExt;
+
+            metadata.ino()
+        };
+
+        #[cfg(not(unix))]
+        let ino = 0u64;
+
+        let tag = format!(
+            "{:x}:{:x}:{:x}:{:x}",
+            ino,
+            metadata.len(),
+            time.as_secs(),
+            time.subsec_nanos()
+        );
+
+        Some(EntityTag {
+            weak: false, tag: Cow::from(tag)
+        })
+    }
+}
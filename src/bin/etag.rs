@@ -0,0 +1,203 @@
+//! Compute, validate and compare entity tags, and print the validators of a URL.
+
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use entity_tag::{EntityTag, EntityTagWriter};
+
+#[derive(Debug, Parser)]
+#[command(name = "etag", version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compute the entity tags of files
+    File {
+        /// How the entity tags are computed
+        #[arg(short, long, value_enum, default_value = "content")]
+        scheme:     Scheme,
+        /// The number of bytes read from both ends of a file by the `sampled` scheme
+        #[arg(long, default_value = "65536")]
+        sample_len: u64,
+        /// How the entity tags are printed
+        #[arg(short, long, value_enum, default_value = "etag")]
+        format:     Format,
+        #[arg(required = true)]
+        paths:      Vec<PathBuf>,
+    },
+    /// Check whether entity tags are valid
+    Check {
+        #[arg(required = true)]
+        etags: Vec<String>,
+    },
+    /// Compare two entity tags with the strong comparison and the weak comparison
+    Compare { a: String, b: String },
+    /// Fetch a URL and print its validators
+    Fetch { url: String },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Scheme {
+    /// Hash the content (strong)
+    Content,
+    /// Use the length and the modified time (weak)
+    Meta,
+    /// Hash the length, the head and the tail (weak)
+    Sampled,
+    /// Use the format of `actix-files` (strong)
+    ActixFiles,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// `W/"..."`
+    Etag,
+    /// `ETag: W/"..."`
+    Header,
+    /// The opaque tag without double quotes
+    Tag,
+}
+
+fn compute(path: &Path, scheme: Scheme, sample_len: u64) -> io::Result<EntityTag<'static>> {
+    match scheme {
+        Scheme::Content => {
+            let mut writer = EntityTagWriter::new(io::sink());
+
+            io::copy(&mut File::open(path)?, &mut writer)?;
+
+            Ok(writer.etag())
+        },
+        Scheme::Meta => Ok(EntityTag::from_file_meta(&fs::metadata(path)?)),
+        Scheme::Sampled => EntityTag::from_reader_sampled(&mut File::open(path)?, sample_len),
+        Scheme::ActixFiles => EntityTag::from_file_meta_actix_files(&fs::metadata(path)?)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::Other, "the modified time is unavailable")
+            }),
+    }
+}
+
+fn format(etag: &EntityTag, format: Format) -> String {
+    match format {
+        Format::Etag => etag.to_string(),
+        Format::Header => etag.to_header_string(),
+        Format::Tag => String::from(etag.get_tag()),
+    }
+}
+
+fn parse(etag: &str) -> Result<EntityTag<'_>, String> {
+    EntityTag::from_str(etag).map_err(|error| format!("{}: {}", etag, error))
+}
+
+fn fetch(url: &str) -> Result<(), ureq::Error> {
+    let response = ureq::get(url).call()?;
+
+    println!("{}", response.status());
+
+    for name in ["etag", "last-modified", "cache-control", "vary"] {
+        for value in response.headers().get_all(name) {
+            println!("{}: {}", name, String::from_utf8_lossy(value.as_bytes()));
+        }
+    }
+
+    Ok(())
+}
+
+fn run(args: Args) -> bool {
+    match args.command {
+        Command::File {
+            scheme,
+            sample_len,
+            format: output,
+            paths,
+        } => {
+            let mut ok = true;
+
+            // print the paths only when there are several files, so that the output for a single file is the bare entity tag, ready to be used in a header field or a script
+            let show_path = paths.len() > 1;
+
+            for path in paths {
+                match compute(&path, scheme, sample_len) {
+                    Ok(etag) => {
+                        if show_path {
+                            println!("{}  {}", format(&etag, output), path.display());
+                        } else {
+                            println!("{}", format(&etag, output));
+                        }
+                    },
+                    Err(error) => {
+                        eprintln!("{}: {}", path.display(), error);
+
+                        ok = false;
+                    },
+                }
+            }
+
+            ok
+        },
+        Command::Check {
+            etags,
+        } => {
+            let mut ok = true;
+
+            for etag in etags {
+                match parse(&etag) {
+                    Ok(parsed) => println!(
+                        "{}: valid, {}, opaque tag `{}`",
+                        etag,
+                        if parsed.weak { "weak" } else { "strong" },
+                        parsed.get_tag()
+                    ),
+                    Err(error) => {
+                        eprintln!("{}", error);
+
+                        ok = false;
+                    },
+                }
+            }
+
+            ok
+        },
+        Command::Compare {
+            a,
+            b,
+        } => match (parse(&a), parse(&b)) {
+            (Ok(a), Ok(b)) => {
+                println!("strong comparison: {}", a.strong_eq(&b));
+                println!("weak comparison: {}", a.weak_eq(&b));
+
+                true
+            },
+            (a, b) => {
+                for error in [a.err(), b.err()].iter().flatten() {
+                    eprintln!("{}", error);
+                }
+
+                false
+            },
+        },
+        Command::Fetch {
+            url,
+        } => match fetch(&url) {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!("{}: {}", url, error);
+
+                false
+            },
+        },
+    }
+}
+
+fn main() {
+    if !run(Args::parse()) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{BinaryDecodeError, EntityTag, EntityTagError};
+
+const WEAK_FLAG: u8 = 0b1;
+
+/// Append a length as an unsigned LEB128 integer.
+pub(crate) fn write_length(bytes: &mut Vec<u8>, mut length: usize) {
+    loop {
+        let b = (length & 0x7F) as u8;
+
+        length >>= 7;
+
+        if length == 0 {
+            bytes.push(b);
+
+            break;
+        }
+
+        bytes.push(b | 0x80);
+    }
+}
+
+/// Read a length encoded as an unsigned LEB128 integer at the beginning of `bytes`. Returns the length and the number of bytes it occupied.
+pub(crate) fn read_length(bytes: &[u8]) -> Result<(usize, usize), BinaryDecodeError> {
+    let mut length: usize = 0;
+    let mut p = 0;
+
+    loop {
+        let b = *bytes.get(p).ok_or(BinaryDecodeError::UnexpectedEnd)?;
+
+        let value = (b & 0x7F) as usize;
+        let shift = p * 7;
+
+        // reject the bits which do not fit into `usize`
+        if shift >= usize::BITS as usize || value << shift >> shift != value {
+            return Err(BinaryDecodeError::InvalidLength);
+        }
+
+        length |= value << shift;
+        p += 1;
+
+        if b & 0x80 == 0 {
+            return Ok((length, p));
+        }
+    }
+}
+
+impl<'t> EntityTag<'t> {
+    /// Encode this entity tag into a compact binary form, for cache index files and wire protocols.
+    ///
+    /// The form consists of a flag byte (the lowest bit is the weakness indicator and the other bits are reserved as zero), the length of the opaque tag as an unsigned LEB128 integer, and the bytes of the opaque tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tag = self.get_tag_cow().as_bytes();
+
+        let mut bytes = Vec::with_capacity(tag.len() + 4);
+
+        bytes.push(if self.weak { WEAK_FLAG } else { 0 });
+
+        write_length(&mut bytes, tag.len());
+
+        bytes.extend_from_slice(tag);
+
+        bytes
+    }
+
+    /// Decode an entity tag from the compact binary form produced by `to_bytes`, at the beginning of `bytes`. The opaque tag is borrowed from `bytes` and validated. Returns the entity tag and the number of bytes it occupied.
+    pub fn from_bytes_encoded<S: ?Sized + AsRef<[u8]>>(
+        bytes: &'t S,
+    ) -> Result<(Self, usize), BinaryDecodeError> {
+        let bytes = bytes.as_ref();
+
+        let flags = *bytes.first().ok_or(BinaryDecodeError::UnexpectedEnd)?;
+
+        if flags & !WEAK_FLAG != 0 {
+            return Err(BinaryDecodeError::InvalidFlags);
+        }
+
+        let (length, n) = read_length(&bytes[1..])?;
+        let p = 1 + n;
+
+        let end = p.checked_add(length).ok_or(BinaryDecodeError::InvalidLength)?;
+
+        let tag = bytes.get(p..end).ok_or(BinaryDecodeError::UnexpectedEnd)?;
+        let tag = core::str::from_utf8(tag).map_err(|_| EntityTagError::InvalidTag)?;
+
+        Self::check_unquoted_tag(tag)?;
+
+        Ok((
+            EntityTag {
+                weak: flags & WEAK_FLAG != 0, tag: Cow::from(tag)
+            },
+            end,
+        ))
+    }
+}
@@ -0,0 +1,46 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::EntityTagError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Possible errors of decoding an `EntityTag` from its compact binary form.
+pub enum BinaryDecodeError {
+    /// The data ends unexpectedly.
+    UnexpectedEnd,
+    /// The flag byte has reserved bits set.
+    InvalidFlags,
+    /// The length prefix is malformed or too large.
+    InvalidLength,
+    /// The tag bytes are not a valid opaque tag.
+    InvalidEntityTag(EntityTagError),
+    /// The data does not start with the header of an `EntityTagStore` file, or the version is not supported.
+    InvalidHeader,
+    /// A key of an `EntityTagStore` file is not valid UTF-8.
+    InvalidKey,
+}
+
+impl From<EntityTagError> for BinaryDecodeError {
+    #[inline]
+    fn from(error: EntityTagError) -> Self {
+        BinaryDecodeError::InvalidEntityTag(error)
+    }
+}
+
+impl Display for BinaryDecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            BinaryDecodeError::UnexpectedEnd => f.write_str("the data ends unexpectedly"),
+            BinaryDecodeError::InvalidFlags => f.write_str("invalid flags"),
+            BinaryDecodeError::InvalidLength => f.write_str("invalid length"),
+            BinaryDecodeError::InvalidEntityTag(error) => Display::fmt(error, f),
+            BinaryDecodeError::InvalidHeader => f.write_str("invalid header"),
+            BinaryDecodeError::InvalidKey => f.write_str("invalid key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BinaryDecodeError {}
@@ -0,0 +1,189 @@
+use core::fmt::{self, Alignment, Formatter, Write};
+
+#[cfg(feature = "alloc")]
+use crate::EntityTag;
+use crate::{BufferTooSmall, EntityTagRef};
+
+/// The length of a formatted entity tag.
+#[inline]
+pub(crate) fn formatted_len(weak: bool, tag: &str) -> usize {
+    if weak {
+        tag.len() + 4
+    } else {
+        tag.len() + 2
+    }
+}
+
+/// Whether a character of an opaque tag is escaped by the alternate form. Only visible ASCII characters other than `\` are kept.
+#[inline]
+fn needs_escaping(c: char) -> bool {
+    !matches!(c, '\x21'..='\x7E') || c == '\\'
+}
+
+/// The length of an opaque tag in characters after escaping it if `escape` is true.
+fn tag_len(tag: &str, escape: bool) -> usize {
+    if escape {
+        tag.chars()
+            .map(|c| match c {
+                '\\' => 2,
+                c if needs_escaping(c) => c.escape_unicode().len(),
+                _ => 1,
+            })
+            .sum()
+    } else {
+        tag.chars().count()
+    }
+}
+
+/// Write an opaque tag, escaping it if `escape` is true.
+fn write_tag(tag: &str, escape: bool, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+    if !escape || !tag.chars().any(needs_escaping) {
+        return f.write_str(tag);
+    }
+
+    for c in tag.chars() {
+        match c {
+            '\\' => f.write_str("\\\\")?,
+            c if needs_escaping(c) => write!(f, "{}", c.escape_unicode())?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Format an entity tag, honoring the width, fill and alignment flags of the formatter like `Formatter::pad` does.
+///
+/// The alternate flag (`{:#}`) escapes `obs-text` and `\` in the opaque tag as `\u{...}` and `\\`, so that an entity tag supplied by a hostile client cannot corrupt terminals or log parsers.
+pub(crate) fn fmt_entity_tag(
+    weak: bool,
+    tag: &str,
+    f: &mut Formatter<'_>,
+) -> Result<(), fmt::Error> {
+    let escape = f.alternate();
+
+    let padding = match f.width() {
+        Some(width) => {
+            // `obs-text` may contain multi-byte characters
+            let len = tag_len(tag, escape) + if weak { 4 } else { 2 };
+
+            width.saturating_sub(len)
+        },
+        None => 0,
+    };
+
+    let (pre, post) = match f.align() {
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, (padding + 1) / 2),
+        Some(Alignment::Left) | None => (0, padding),
+    };
+
+    let fill = f.fill();
+
+    for _ in 0..pre {
+        f.write_char(fill)?;
+    }
+
+    if weak {
+        f.write_str("W/")?;
+    }
+
+    f.write_char('"')?;
+    write_tag(tag, escape, f)?;
+    f.write_char('"')?;
+
+    for _ in 0..post {
+        f.write_char(fill)?;
+    }
+
+    Ok(())
+}
+
+/// Format an entity tag into the beginning of `buf`, returning the number of written bytes.
+pub(crate) fn write_to_slice(
+    weak: bool,
+    tag: &str,
+    buf: &mut [u8],
+) -> Result<usize, BufferTooSmall> {
+    let len = formatted_len(weak, tag);
+
+    if buf.len() < len {
+        return Err(BufferTooSmall::new(len));
+    }
+
+    let start = if weak {
+        buf[..3].copy_from_slice(b"W/\"");
+
+        3
+    } else {
+        buf[0] = b'"';
+
+        1
+    };
+
+    buf[start..len - 1].copy_from_slice(tag.as_bytes());
+    buf[len - 1] = b'"';
+
+    Ok(len)
+}
+
+/// Format an entity tag into a `heapless::String`.
+#[cfg(feature = "heapless")]
+fn to_heapless_string<const N: usize>(
+    weak: bool,
+    tag: &str,
+) -> Result<heapless::String<N>, BufferTooSmall> {
+    let len = formatted_len(weak, tag);
+
+    if len > N {
+        return Err(BufferTooSmall::new(len));
+    }
+
+    let mut s = heapless::String::new();
+
+    // the capacity has been checked
+    if weak {
+        s.push_str("W/").unwrap();
+    }
+
+    s.push('"').unwrap();
+    s.push_str(tag).unwrap();
+    s.push('"').unwrap();
+
+    Ok(s)
+}
+
+#[cfg(feature = "alloc")]
+impl<'t> EntityTag<'t> {
+    /// Format this entity tag, such as `W/"xyzzy"`, into the beginning of a byte buffer without allocating. Returns the number of written bytes.
+    #[inline]
+    pub fn write_to_slice(&self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        write_to_slice(self.weak, self.get_tag_cow(), buf)
+    }
+
+    #[cfg(feature = "heapless")]
+    /// Format this entity tag, such as `W/"xyzzy"`, into a `heapless::String`.
+    #[inline]
+    pub fn to_heapless_string<const N: usize>(
+        &self,
+    ) -> Result<heapless::String<N>, BufferTooSmall> {
+        to_heapless_string(self.weak, self.get_tag_cow())
+    }
+}
+
+impl<'t> EntityTagRef<'t> {
+    /// Format this entity tag, such as `W/"xyzzy"`, into the beginning of a byte buffer. Returns the number of written bytes.
+    #[inline]
+    pub fn write_to_slice(&self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        write_to_slice(self.weak, self.get_tag(), buf)
+    }
+
+    #[cfg(feature = "heapless")]
+    /// Format this entity tag, such as `W/"xyzzy"`, into a `heapless::String`.
+    #[inline]
+    pub fn to_heapless_string<const N: usize>(
+        &self,
+    ) -> Result<heapless::String<N>, BufferTooSmall> {
+        to_heapless_string(self.weak, self.get_tag())
+    }
+}
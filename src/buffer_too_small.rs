@@ -0,0 +1,34 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// The error of formatting an entity tag into a buffer which is too small.
+pub struct BufferTooSmall {
+    required: usize,
+}
+
+impl BufferTooSmall {
+    #[inline]
+    pub(crate) const fn new(required: usize) -> Self {
+        BufferTooSmall {
+            required,
+        }
+    }
+
+    /// The number of bytes that the formatted entity tag needs.
+    #[inline]
+    pub const fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl Display for BufferTooSmall {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_fmt(format_args!("the buffer is too small, {} bytes are required", self.required))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BufferTooSmall {}
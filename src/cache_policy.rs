@@ -0,0 +1,215 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::EntityTag;
+
+/// The caching header fields of a response, which bundles the entity tag with the `Cache-Control` directives, defined in [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-5.2), so that the validator and the freshness policy are always set together.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CachePolicy<'t> {
+    etag:                   EntityTag<'t>,
+    public:                 bool,
+    private:                bool,
+    no_cache:               bool,
+    no_store:               bool,
+    max_age:                Option<u64>,
+    s_maxage:               Option<u64>,
+    must_revalidate:        bool,
+    immutable:              bool,
+    stale_while_revalidate: Option<u64>,
+    last_modified:          Option<String>,
+    vary:                   Vec<&'t str>,
+}
+
+impl<'t> CachePolicy<'t> {
+    /// Create a policy for a response with the given entity tag and no `Cache-Control` directives.
+    #[inline]
+    pub fn new(etag: EntityTag<'t>) -> Self {
+        CachePolicy {
+            etag,
+            public: false,
+            private: false,
+            no_cache: false,
+            no_store: false,
+            max_age: None,
+            s_maxage: None,
+            must_revalidate: false,
+            immutable: false,
+            stale_while_revalidate: None,
+            last_modified: None,
+            vary: Vec::new(),
+        }
+    }
+
+    /// Add the `public` directive.
+    #[inline]
+    pub fn public(mut self) -> Self {
+        self.public = true;
+
+        self
+    }
+
+    /// Add the `private` directive.
+    #[inline]
+    pub fn private(mut self) -> Self {
+        self.private = true;
+
+        self
+    }
+
+    /// Add the `no-cache` directive, which makes caches revalidate the response with the entity tag before reusing it.
+    #[inline]
+    pub fn no_cache(mut self) -> Self {
+        self.no_cache = true;
+
+        self
+    }
+
+    /// Add the `no-store` directive.
+    #[inline]
+    pub fn no_store(mut self) -> Self {
+        self.no_store = true;
+
+        self
+    }
+
+    /// Add the `max-age` directive in seconds.
+    #[inline]
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+
+        self
+    }
+
+    /// Add the `s-maxage` directive in seconds.
+    #[inline]
+    pub fn s_maxage(mut self, seconds: u64) -> Self {
+        self.s_maxage = Some(seconds);
+
+        self
+    }
+
+    /// Add the `must-revalidate` directive.
+    #[inline]
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+
+        self
+    }
+
+    /// Add the `immutable` directive, defined in [RFC8246](https://www.rfc-editor.org/rfc/rfc8246).
+    #[inline]
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+
+        self
+    }
+
+    /// Add the `stale-while-revalidate` directive in seconds, defined in [RFC5861](https://www.rfc-editor.org/rfc/rfc5861).
+    #[inline]
+    pub fn stale_while_revalidate(mut self, seconds: u64) -> Self {
+        self.stale_while_revalidate = Some(seconds);
+
+        self
+    }
+
+    /// Also emit the `Last-Modified` header field.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn last_modified(mut self, time: SystemTime) -> Self {
+        self.last_modified = Some(crate::http_date::format_http_date(time));
+
+        self
+    }
+
+    /// Add a header field name to the `Vary` header field.
+    #[inline]
+    pub fn vary(mut self, name: &'t str) -> Self {
+        self.vary.push(name);
+
+        self
+    }
+
+    /// Get the entity tag.
+    #[inline]
+    pub fn etag(&self) -> &EntityTag<'t> {
+        &self.etag
+    }
+
+    /// Format the `Cache-Control` field value, such as `public, max-age=60`.
+    pub fn cache_control(&self) -> String {
+        let mut directives: Vec<String> = Vec::new();
+
+        let flags = [
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+        ];
+
+        directives
+            .extend(flags.iter().filter(|(set, _)| *set).map(|(_, name)| String::from(*name)));
+
+        if let Some(seconds) = self.max_age {
+            directives.push(format!("max-age={}", seconds));
+        }
+
+        if let Some(seconds) = self.s_maxage {
+            directives.push(format!("s-maxage={}", seconds));
+        }
+
+        if self.must_revalidate {
+            directives.push(String::from("must-revalidate"));
+        }
+
+        if self.immutable {
+            directives.push(String::from("immutable"));
+        }
+
+        if let Some(seconds) = self.stale_while_revalidate {
+            directives.push(format!("stale-while-revalidate={}", seconds));
+        }
+
+        directives.join(", ")
+    }
+
+    /// Build the caching header fields of the response as pairs of lowercase field names and field values. `ETag` is always present, `Cache-Control` is present if there is any directive, and `Last-Modified` and `Vary` are present if they are set.
+    pub fn to_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::with_capacity(4);
+
+        headers.push(("etag", self.etag.to_string()));
+
+        let cache_control = self.cache_control();
+
+        if !cache_control.is_empty() {
+            headers.push(("cache-control", cache_control));
+        }
+
+        if let Some(last_modified) = self.last_modified.as_ref() {
+            headers.push(("last-modified", last_modified.clone()));
+        }
+
+        if !self.vary.is_empty() {
+            headers.push(("vary", self.vary.join(", ")));
+        }
+
+        headers
+    }
+
+    /// Insert the caching header fields of the response into a `HeaderMap`, replacing the existing ones. The entity tag is always inserted, while a `Vary` field value which is not a valid header value is skipped.
+    #[cfg(feature = "http")]
+    pub fn apply_to(&self, headers: &mut http::HeaderMap) {
+        headers.insert(http::header::ETAG, http::HeaderValue::from(&self.etag));
+
+        for (name, value) in self.to_headers().into_iter().filter(|(name, _)| *name != "etag") {
+            // the directives and the date are valid header values, and the `Vary` field names are supposed to be tokens
+            if let Ok(value) = http::HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use alloc::string::String;
+
+use crate::{EntityTag, EntityTagError, EntityTagRef, ParseOptions};
+
+impl<'t> EntityTag<'t> {
+    /// Parse a sloppily formatted entity tag, such as ` w/"xyzzy" ` or `xyzzy`, and return its canonical form, such as `W/"xyzzy"`, so that proxies can compare and re-emit normalized validators.
+    ///
+    /// The surrounding whitespace is removed, the weakness indicator is uppercased, and the opaque tag is quoted exactly once.
+    #[inline]
+    pub fn canonicalize<S: ?Sized + AsRef<str>>(etag: &S) -> Result<String, EntityTagError> {
+        let etag = EntityTagRef::from_str_with(etag, ParseOptions::lenient())?;
+
+        let mut s = String::with_capacity(crate::buffer::formatted_len(etag.weak, etag.get_tag()));
+
+        if etag.weak {
+            s.push_str("W/");
+        }
+
+        s.push('"');
+        s.push_str(etag.get_tag());
+        s.push('"');
+
+        Ok(s)
+    }
+}
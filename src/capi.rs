@@ -0,0 +1,135 @@
+//! A C ABI for constructing, parsing, comparing and formatting entity tags.
+//!
+//! Entity tags are passed as opaque pointers which are created by `entity_tag_new`, `entity_tag_parse` or `entity_tag_from_data` and must be released by `entity_tag_free`. Strings are passed as pointers to UTF-8 bytes with lengths, so they do not need to be NUL-terminated.
+//!
+//! Build the crate as a `staticlib` or a `cdylib` with this feature enabled, such as `cargo rustc --release --features capi --crate-type staticlib`, to link it into C/C++ programs. The declarations are in `include/entity_tag.h`.
+
+use alloc::boxed::Box;
+use core::{ptr, slice, str};
+
+use crate::EntityTag;
+
+/// The opaque type of entity tags for C.
+pub type EntityTagHandle = EntityTag<'static>;
+
+unsafe fn as_str<'a>(s: *const u8, len: usize) -> Option<&'a str> {
+    if s.is_null() {
+        return if len == 0 { Some("") } else { None };
+    }
+
+    str::from_utf8(slice::from_raw_parts(s, len)).ok()
+}
+
+#[inline]
+fn into_handle(etag: EntityTag<'_>) -> *mut EntityTagHandle {
+    Box::into_raw(Box::new(etag.into_owned()))
+}
+
+/// Construct a new entity tag. The double quotes of `tag` are optional. Returns `NULL` if the tag is invalid.
+///
+/// # Safety
+///
+/// `tag` must point to `len` readable bytes, or be `NULL` with `len` being `0`.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_new(
+    weak: bool,
+    tag: *const u8,
+    len: usize,
+) -> *mut EntityTagHandle {
+    match as_str(tag, len).map(|tag| EntityTag::with_str(weak, tag)) {
+        Some(Ok(etag)) => into_handle(etag),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Parse an entity tag, such as `W/"xyzzy"`. Returns `NULL` if the value is invalid.
+///
+/// # Safety
+///
+/// `s` must point to `len` readable bytes, or be `NULL` with `len` being `0`.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_parse(s: *const u8, len: usize) -> *mut EntityTagHandle {
+    match as_str(s, len).map(EntityTag::from_str) {
+        Some(Ok(etag)) => into_handle(etag),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Construct a strong entity tag by hashing data, like `EntityTag::from_data`.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, or be `NULL` with `len` being `0`.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_from_data(data: *const u8, len: usize) -> *mut EntityTagHandle {
+    let data = if data.is_null() { &[] } else { slice::from_raw_parts(data, len) };
+
+    into_handle(EntityTag::from_data(data))
+}
+
+/// Release an entity tag. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `etag` must be created by this module and must not be used after being released.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_free(etag: *mut EntityTagHandle) {
+    if !etag.is_null() {
+        drop(Box::from_raw(etag));
+    }
+}
+
+/// Whether the entity tag is weak.
+///
+/// # Safety
+///
+/// `etag` must be a valid entity tag created by this module.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_is_weak(etag: *const EntityTagHandle) -> bool {
+    (*etag).weak
+}
+
+/// The strong comparison of two entity tags.
+///
+/// # Safety
+///
+/// `a` and `b` must be valid entity tags created by this module.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_strong_eq(
+    a: *const EntityTagHandle,
+    b: *const EntityTagHandle,
+) -> bool {
+    (*a).strong_eq(&*b)
+}
+
+/// The weak comparison of two entity tags.
+///
+/// # Safety
+///
+/// `a` and `b` must be valid entity tags created by this module.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_weak_eq(
+    a: *const EntityTagHandle,
+    b: *const EntityTagHandle,
+) -> bool {
+    (*a).weak_eq(&*b)
+}
+
+/// Format the entity tag, such as `W/"xyzzy"`, into `buf` without a NUL terminator. Returns the length of the formatted entity tag. If it is larger than `len`, nothing is written, so the function can be called again with a large enough buffer.
+///
+/// # Safety
+///
+/// `etag` must be a valid entity tag created by this module. `buf` must point to `len` writable bytes, or be `NULL` with `len` being `0`.
+#[no_mangle]
+pub unsafe extern "C" fn entity_tag_write(
+    etag: *const EntityTagHandle,
+    buf: *mut u8,
+    len: usize,
+) -> usize {
+    let buf: &mut [u8] = if buf.is_null() { &mut [] } else { slice::from_raw_parts_mut(buf, len) };
+
+    match (*etag).write_to_slice(buf) {
+        Ok(n) => n,
+        Err(error) => error.required(),
+    }
+}
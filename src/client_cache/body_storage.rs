@@ -0,0 +1,66 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::convert::Infallible;
+
+/// A storage of the bodies of cached responses, such as files in a directory or a key-value database. The keys are the URLs of the responses.
+pub trait BodyStorage {
+    /// The error type of the storage.
+    type Error;
+
+    /// Load the body stored with the key. Returns `Ok(None)` if there is no such body, e.g. it has been evicted.
+    fn load(&mut self, key: &str) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Store a body with the key, replacing the existing one.
+    fn store(&mut self, key: &str, body: &[u8]) -> Result<(), Self::Error>;
+
+    /// Remove the body stored with the key, if any.
+    fn remove(&mut self, key: &str) -> Result<(), Self::Error>;
+}
+
+/// A `BodyStorage` which keeps the bodies in memory.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct MemoryStorage {
+    bodies: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty storage.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of stored bodies.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Whether there is no stored body.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+}
+
+impl BodyStorage for MemoryStorage {
+    type Error = Infallible;
+
+    #[inline]
+    fn load(&mut self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.bodies.get(key).cloned())
+    }
+
+    #[inline]
+    fn store(&mut self, key: &str, body: &[u8]) -> Result<(), Self::Error> {
+        self.bodies.insert(String::from(key), body.to_vec());
+
+        Ok(())
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.bodies.remove(key);
+
+        Ok(())
+    }
+}
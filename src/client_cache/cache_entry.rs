@@ -0,0 +1,45 @@
+use alloc::string::String;
+
+use crate::{EntityTag, Revalidation};
+
+/// The validators of a cached response.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CacheEntry {
+    pub(crate) etag:          EntityTag<'static>,
+    pub(crate) last_modified: Option<String>,
+}
+
+impl CacheEntry {
+    /// Get the entity tag of the cached response.
+    #[inline]
+    pub const fn etag(&self) -> &EntityTag<'static> {
+        &self.etag
+    }
+
+    /// Get the `Last-Modified` field value of the cached response, if any.
+    #[inline]
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Build the revalidation of the cached response.
+    #[inline]
+    pub fn revalidation(&self) -> Revalidation<'_> {
+        let revalidation = Revalidation::new().etag(self.etag.clone());
+
+        match self.last_modified.as_deref() {
+            Some(last_modified) => revalidation.last_modified(last_modified),
+            None => revalidation,
+        }
+    }
+
+    /// Whether a `304 Not Modified` response with the given entity tag selects this cached response, defined in [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-4.3.4). A strong entity tag needs the strong comparison, and a weak one needs the weak comparison. A response without an entity tag selects it as well.
+    #[inline]
+    pub fn is_selected_by(&self, etag: Option<&EntityTag>) -> bool {
+        match etag {
+            Some(etag) if etag.weak => self.etag.weak_eq(etag),
+            Some(etag) => self.etag.strong_eq(etag),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! A response cache for HTTP clients which revalidates stored responses with their entity tags, defined in [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-4.3).
+
+mod body_storage;
+mod cache_entry;
+mod store;
+
+pub use body_storage::{BodyStorage, MemoryStorage};
+pub use cache_entry::CacheEntry;
+pub use store::ClientCache;
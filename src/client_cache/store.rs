@@ -0,0 +1,113 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use super::{BodyStorage, CacheEntry};
+use crate::{EntityTag, Revalidation};
+
+/// A response cache for HTTP clients keyed by URL. The validators of the cached responses are kept in memory and their bodies are kept in a `BodyStorage`.
+///
+/// Build a conditional request with `revalidation`. Then, cache a `200 OK` response with `store`, or merge a `304 Not Modified` response with the cached one with `merge_not_modified`.
+#[derive(Debug, Clone, Default)]
+pub struct ClientCache<S> {
+    storage: S,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+impl<S: BodyStorage> ClientCache<S> {
+    /// Create an empty cache which keeps bodies in `storage`.
+    #[inline]
+    pub fn new(storage: S) -> Self {
+        ClientCache {
+            storage,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Get the body storage.
+    #[inline]
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Get the validators of the response cached for the URL.
+    #[inline]
+    pub fn entry(&self, url: &str) -> Option<&CacheEntry> {
+        self.entries.get(url)
+    }
+
+    /// Build the revalidation of the response cached for the URL. It is not conditional if nothing is cached.
+    #[inline]
+    pub fn revalidation(&self, url: &str) -> Revalidation<'_> {
+        match self.entries.get(url) {
+            Some(entry) => entry.revalidation(),
+            None => Revalidation::new(),
+        }
+    }
+
+    /// Cache a response for the URL, replacing the existing one. `last_modified` is the `Last-Modified` field value of the response, if any.
+    pub fn store(
+        &mut self,
+        url: &str,
+        etag: EntityTag,
+        last_modified: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), S::Error> {
+        // the old entry must not outlive a failed write of its body
+        self.entries.remove(url);
+
+        self.storage.store(url, body)?;
+
+        self.entries.insert(String::from(url), CacheEntry {
+            etag:          etag.into_owned(),
+            last_modified: last_modified.map(String::from),
+        });
+
+        Ok(())
+    }
+
+    /// Merge a `304 Not Modified` response for the URL with the cached one, and return the cached body. `etag` and `last_modified` are the `ETag` and `Last-Modified` field values of the `304 Not Modified` response, if any. A new `Last-Modified` field value replaces the cached one.
+    ///
+    /// Returns `Ok(None)` if nothing is cached, the cached response is not selected by the entity tag, or the body is missing from the storage. In that case, the entry is dropped and the request should be sent again without conditions.
+    pub fn merge_not_modified(
+        &mut self,
+        url: &str,
+        etag: Option<&EntityTag>,
+        last_modified: Option<&str>,
+    ) -> Result<Option<Vec<u8>>, S::Error> {
+        match self.entries.get(url) {
+            Some(entry) if entry.is_selected_by(etag) => (),
+            Some(_) => {
+                self.remove(url)?;
+
+                return Ok(None);
+            },
+            None => return Ok(None),
+        }
+
+        let body = match self.storage.load(url)? {
+            Some(body) => body,
+            None => {
+                self.entries.remove(url);
+
+                return Ok(None);
+            },
+        };
+
+        if let Some(last_modified) = last_modified {
+            if let Some(entry) = self.entries.get_mut(url) {
+                entry.last_modified = Some(String::from(last_modified));
+            }
+        }
+
+        count!("entity_tag_cache_hits_total", 1, "cache" => "client");
+
+        Ok(Some(body))
+    }
+
+    /// Remove the response cached for the URL, if any.
+    #[inline]
+    pub fn remove(&mut self, url: &str) -> Result<(), S::Error> {
+        self.entries.remove(url);
+
+        self.storage.remove(url)
+    }
+}
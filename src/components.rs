@@ -0,0 +1,72 @@
+use alloc::{borrow::Cow, string::String};
+use core::fmt::{Display, Write};
+
+use crate::{EntityTag, EntityTagError};
+
+impl<'t> EntityTag<'t> {
+    /// Construct a new EntityTag whose opaque tag consists of components separated by `delimiter`, such as `<size>-<hash>`. Returns an error if the result is not a valid opaque tag, or if a component contains the delimiter, which would make the components ambiguous.
+    ///
+    /// ```rust
+    /// use entity_tag::EntityTag;
+    ///
+    /// let size = 1024u64;
+    ///
+    /// let etag = EntityTag::from_components(false, '-', [
+    ///     size.to_string().as_str(),
+    ///     "abc",
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!("\"1024-abc\"", etag.to_string());
+    ///
+    /// let [size, hash] = etag.components::<2>('-').unwrap();
+    ///
+    /// assert_eq!(Ok(1024u64), size.parse());
+    /// assert_eq!("abc", hash);
+    /// ```
+    pub fn from_components<I: IntoIterator<Item = S>, S: Display>(
+        weak: bool,
+        delimiter: char,
+        components: I,
+    ) -> Result<EntityTag<'static>, EntityTagError> {
+        let mut tag = String::new();
+
+        for (i, component) in components.into_iter().enumerate() {
+            if i > 0 {
+                tag.push(delimiter);
+            }
+
+            let start = tag.len();
+
+            write!(tag, "{}", component).map_err(|_| EntityTagError::InvalidTag)?;
+
+            if tag[start..].contains(delimiter) {
+                return Err(EntityTagError::InvalidTag);
+            }
+        }
+
+        Self::check_unquoted_tag(&tag)?;
+
+        Ok(EntityTag {
+            weak,
+            tag: Cow::from(tag),
+        })
+    }
+
+    /// Split the opaque tag into exactly `N` components separated by `delimiter`. Returns `None` if the number of the components is not `N`.
+    pub fn components<const N: usize>(&self, delimiter: char) -> Option<[&str; N]> {
+        let mut parts = self.tag.split(delimiter);
+
+        let mut components = [""; N];
+
+        for component in components.iter_mut() {
+            *component = parts.next()?;
+        }
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(components)
+    }
+}
@@ -0,0 +1,22 @@
+use defmt::{Format, Formatter};
+
+#[cfg(feature = "alloc")]
+use crate::EntityTag;
+use crate::EntityTagRef;
+
+#[cfg(feature = "alloc")]
+impl<'t> Format for EntityTag<'t> {
+    #[inline]
+    fn format(&self, fmt: Formatter<'_>) {
+        Format::format(&self.as_entity_tag_ref(), fmt)
+    }
+}
+
+impl<'t> Format for EntityTagRef<'t> {
+    #[inline]
+    fn format(&self, fmt: Formatter<'_>) {
+        let prefix = if self.weak { "W/" } else { "" };
+
+        defmt::write!(fmt, "{=str}\"{=str}\"", prefix, self.get_tag())
+    }
+}
@@ -0,0 +1,43 @@
+/// A hashing algorithm of the `Repr-Digest` and `Content-Digest` header fields, defined in [RFC9530](https://www.rfc-editor.org/rfc/rfc9530#section-5), or of [Subresource Integrity](https://www.w3.org/TR/SRI/).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DigestAlgorithm {
+    /// SHA-256, the key of which is `sha-256`.
+    Sha256,
+    /// SHA-384. Only used by Subresource Integrity. It is not registered for the digest fields.
+    Sha384,
+    /// SHA-512, the key of which is `sha-512`.
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Get the key of the algorithm used in the digest fields, such as `sha-256`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha-256",
+            DigestAlgorithm::Sha384 => "sha-384",
+            DigestAlgorithm::Sha512 => "sha-512",
+        }
+    }
+
+    /// Get the name of the algorithm used by Subresource Integrity, such as `sha256`.
+    #[inline]
+    pub const fn sri_name(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha384 => "sha384",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Get the length of the digests in bytes.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 => 32,
+            DigestAlgorithm::Sha384 => 48,
+            DigestAlgorithm::Sha512 => 64,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Possible errors of converting digests into entity tags.
+pub enum DigestError {
+    /// The field value has no digest of the requested algorithm.
+    MissingAlgorithm,
+    /// The digest is not a valid byte sequence of the expected length.
+    InvalidDigest,
+}
+
+impl Display for DigestError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            DigestError::MissingAlgorithm => {
+                f.write_str("the field value has no digest of the algorithm")
+            },
+            DigestError::InvalidDigest => f.write_str("invalid digest"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DigestError {}
@@ -0,0 +1,165 @@
+//! Interoperability with the `Repr-Digest` and `Content-Digest` header fields, defined in [RFC9530](https://www.rfc-editor.org/rfc/rfc9530), and with [Subresource Integrity](https://www.w3.org/TR/SRI/) metadata.
+//!
+//! An entity tag derived from a digest is strong, and its opaque tag is the digest encoded in padded standard Base64, which is the same encoding used by the digest fields and Subresource Integrity. Therefore the digest does not need to be computed twice.
+
+mod digest_algorithm;
+mod digest_error;
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use base64::{
+    alphabet,
+    engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+pub use digest_algorithm::DigestAlgorithm;
+pub use digest_error::DigestError;
+
+use crate::EntityTag;
+
+/// The standard Base64 engine which accepts both padded and unpadded input.
+const LENIENT_STANDARD: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+pub(crate) fn decode_digest(s: &str, algorithm: DigestAlgorithm) -> Result<Vec<u8>, DigestError> {
+    match LENIENT_STANDARD.decode(s) {
+        Ok(digest) if digest.len() == algorithm.len() => Ok(digest),
+        _ => Err(DigestError::InvalidDigest),
+    }
+}
+
+/// Find the digest of the given algorithm in a `Repr-Digest` or `Content-Digest` field value.
+pub fn parse_digest_field(value: &str, algorithm: DigestAlgorithm) -> Result<Vec<u8>, DigestError> {
+    for member in value.split(',') {
+        // parameters are ignored
+        let member = member.split(';').next().unwrap_or("").trim_matches(|c| c == ' ' || c == '\t');
+
+        if let Some((key, value)) = member.split_once('=') {
+            if key == algorithm.as_str() {
+                let value = value
+                    .strip_prefix(':')
+                    .and_then(|value| value.strip_suffix(':'))
+                    .ok_or(DigestError::InvalidDigest)?;
+
+                return decode_digest(value, algorithm);
+            }
+        }
+    }
+
+    Err(DigestError::MissingAlgorithm)
+}
+
+/// Format digests into a `Repr-Digest` or `Content-Digest` field value, such as `sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:`.
+pub fn format_digest_field(digests: &[(DigestAlgorithm, &[u8])]) -> String {
+    let mut s = String::new();
+
+    for (algorithm, digest) in digests {
+        if !s.is_empty() {
+            s.push_str(", ");
+        }
+
+        s.push_str(algorithm.as_str());
+        s.push_str("=:");
+        general_purpose::STANDARD.encode_string(digest, &mut s);
+        s.push(':');
+    }
+
+    s
+}
+
+/// Find the digest of the strongest supported algorithm in Subresource Integrity metadata, such as `sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC`.
+pub fn parse_sri(metadata: &str) -> Result<(DigestAlgorithm, Vec<u8>), DigestError> {
+    let mut strongest: Option<(DigestAlgorithm, &str)> = None;
+
+    for hash in metadata.split_ascii_whitespace() {
+        // options are ignored
+        let hash = hash.split('?').next().unwrap_or("");
+
+        if let Some((name, digest)) = hash.split_once('-') {
+            let algorithm = match name {
+                "sha256" => DigestAlgorithm::Sha256,
+                "sha384" => DigestAlgorithm::Sha384,
+                "sha512" => DigestAlgorithm::Sha512,
+                _ => continue,
+            };
+
+            let stronger = match strongest {
+                Some((strongest_algorithm, _)) => algorithm.len() > strongest_algorithm.len(),
+                None => true,
+            };
+
+            if stronger {
+                strongest = Some((algorithm, digest));
+            }
+        }
+    }
+
+    match strongest {
+        Some((algorithm, digest)) => Ok((algorithm, decode_digest(digest, algorithm)?)),
+        None => Err(DigestError::MissingAlgorithm),
+    }
+}
+
+impl<'t> EntityTag<'t> {
+    /// Construct a strong EntityTag from a digest.
+    #[inline]
+    pub fn from_digest<S: ?Sized + AsRef<[u8]>>(digest: &S) -> EntityTag<'static> {
+        let tag = general_purpose::STANDARD.encode(digest);
+
+        EntityTag {
+            weak: false, tag: Cow::from(tag)
+        }
+    }
+
+    /// Construct a strong EntityTag from the digest of the given algorithm in a `Repr-Digest` or `Content-Digest` field value.
+    #[inline]
+    pub fn from_digest_field<S: ?Sized + AsRef<str>>(
+        value: &S,
+        algorithm: DigestAlgorithm,
+    ) -> Result<EntityTag<'static>, DigestError> {
+        let digest = parse_digest_field(value.as_ref(), algorithm)?;
+
+        Ok(Self::from_digest(&digest))
+    }
+
+    /// Format this entity tag into a `Repr-Digest` or `Content-Digest` field value, if it is a strong entity tag constructed from a digest of the given algorithm.
+    #[inline]
+    pub fn to_digest_field(&self, algorithm: DigestAlgorithm) -> Option<String> {
+        if self.weak {
+            return None;
+        }
+
+        let digest = decode_digest(self.get_tag_cow(), algorithm).ok()?;
+
+        Some(format_digest_field(&[(algorithm, &digest)]))
+    }
+
+    /// Construct a strong EntityTag from Subresource Integrity metadata, such as `sha256-X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=`. If there are several hashes, the one of the strongest algorithm is used.
+    #[inline]
+    pub fn from_sri<S: ?Sized + AsRef<str>>(
+        metadata: &S,
+    ) -> Result<EntityTag<'static>, DigestError> {
+        let (_, digest) = parse_sri(metadata.as_ref())?;
+
+        Ok(Self::from_digest(&digest))
+    }
+
+    /// Format this entity tag into Subresource Integrity metadata, if it is a strong entity tag constructed from a digest of the given algorithm.
+    #[inline]
+    pub fn to_sri(&self, algorithm: DigestAlgorithm) -> Option<String> {
+        if self.weak {
+            return None;
+        }
+
+        let digest = decode_digest(self.get_tag_cow(), algorithm).ok()?;
+
+        let mut s = String::from(algorithm.sri_name());
+
+        s.push('-');
+        general_purpose::STANDARD.encode_string(digest, &mut s);
+
+        Some(s)
+    }
+}
@@ -0,0 +1,27 @@
+use core::hash::Hasher;
+
+use embedded_io::Read;
+use highway::HighwayHasher;
+
+use crate::{epoch::salt, EntityTag};
+
+impl<'t> EntityTag<'t> {
+    /// Construct a strong EntityTag by reading all the data from an `embedded_io::Read` reader in small chunks, which is the same as `from_data` with the whole data.
+    pub fn from_reader<R: Read + ?Sized>(reader: &mut R) -> Result<EntityTag<'static>, R::Error> {
+        let mut hasher = HighwayHasher::default();
+        let mut buf = [0u8; 256];
+
+        loop {
+            match reader.read(&mut buf)? {
+                0 => break,
+                n => {
+                    count!("entity_tag_hashed_bytes_total", n);
+
+                    hasher.write(&buf[..n]);
+                },
+            }
+        }
+
+        Ok(Self::from_hash(false, salt(hasher.finish())))
+    }
+}
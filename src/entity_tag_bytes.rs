@@ -0,0 +1,203 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::{EntityTag, EntityTagError, EntityTagRef};
+
+/// An entity tag whose opaque tag is stored as bytes, so that `obs-text` octets which are not valid UTF-8 can be represented, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3). This lets proxies pass such entity tags through faithfully.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct EntityTagBytes<'t> {
+    /// Whether to have a weakness indicator.
+    pub weak: bool,
+    /// *etagc
+    tag:      Cow<'t, [u8]>,
+}
+
+impl<'t> EntityTagBytes<'t> {
+    /// Construct a new EntityTagBytes without checking.
+    #[allow(clippy::missing_safety_doc)]
+    #[inline]
+    pub const unsafe fn new_unchecked(weak: bool, tag: Cow<'t, [u8]>) -> Self {
+        EntityTagBytes {
+            weak,
+            tag,
+        }
+    }
+
+    /// Construct a new EntityTagBytes. The double quotes of `tag` are optional.
+    pub fn with_bytes<S: ?Sized + AsRef<[u8]>>(
+        weak: bool,
+        tag: &'t S,
+    ) -> Result<Self, EntityTagError> {
+        let tag = tag.as_ref();
+
+        let tag = match tag.strip_prefix(b"\"") {
+            Some(tag) => {
+                tag.strip_suffix(b"\"").ok_or(EntityTagError::MissingClosingDoubleQuote)?
+            },
+            None => tag,
+        };
+
+        EntityTagRef::check_unquoted_bytes(tag)?;
+
+        Ok(EntityTagBytes {
+            weak,
+            tag: Cow::from(tag),
+        })
+    }
+
+    /// Parse and construct a new EntityTagBytes from bytes, such as the value of an `ETag` header field.
+    pub fn from_bytes<S: ?Sized + AsRef<[u8]>>(etag: &'t S) -> Result<Self, EntityTagError> {
+        let s = etag.as_ref();
+
+        let (weak, s) = match s.strip_prefix(b"W/") {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let s = s.strip_prefix(b"\"").ok_or(EntityTagError::MissingStartingDoubleQuote)?;
+        let tag = s.strip_suffix(b"\"").ok_or(EntityTagError::MissingClosingDoubleQuote)?;
+
+        EntityTagRef::check_unquoted_bytes(tag)?;
+
+        Ok(EntityTagBytes {
+            weak,
+            tag: Cow::from(tag),
+        })
+    }
+
+    /// Get the tag. The double quotes are not included.
+    #[inline]
+    pub fn get_tag(&self) -> &[u8] {
+        self.tag.as_ref()
+    }
+
+    /// Format this entity tag, such as `W/"xyzzy"`, into bytes without any escaping, as the value of an `ETag` header field.
+    pub fn to_header_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.tag.len() + 4);
+
+        if self.weak {
+            bytes.extend_from_slice(b"W/");
+        }
+
+        bytes.push(b'"');
+        bytes.extend_from_slice(&self.tag);
+        bytes.push(b'"');
+
+        bytes
+    }
+
+    /// Convert this entity tag into an `EntityTag` if its opaque tag is valid UTF-8.
+    #[inline]
+    pub fn to_entity_tag(&self) -> Result<EntityTag<'_>, EntityTagError> {
+        let tag = core::str::from_utf8(&self.tag).map_err(|_| EntityTagError::InvalidTag)?;
+
+        Ok(EntityTagRef {
+            weak: self.weak,
+            tag,
+        }
+        .into())
+    }
+
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> EntityTagBytes<'static> {
+        EntityTagBytes {
+            weak: self.weak, tag: Cow::from(self.tag.into_owned())
+        }
+    }
+}
+
+impl<'t> EntityTagBytes<'t> {
+    /// For strong comparison two entity-tags are equivalent if both are not weak and their opaque-tags match octet-by-octet.
+    #[inline]
+    pub fn strong_eq(&self, other: &EntityTagBytes) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// For weak comparison two entity-tags are equivalent if their opaque-tags match octet-by-octet, regardless of either or both being tagged as "weak".
+    #[inline]
+    pub fn weak_eq(&self, other: &EntityTagBytes) -> bool {
+        self.tag == other.tag
+    }
+
+    /// The inverse of `strong_eq`.
+    #[inline]
+    pub fn strong_ne(&self, other: &EntityTagBytes) -> bool {
+        !self.strong_eq(other)
+    }
+
+    /// The inverse of `weak_eq`.
+    #[inline]
+    pub fn weak_ne(&self, other: &EntityTagBytes) -> bool {
+        !self.weak_eq(other)
+    }
+}
+
+impl<'t> From<EntityTag<'t>> for EntityTagBytes<'t> {
+    #[inline]
+    fn from(etag: EntityTag<'t>) -> Self {
+        let weak = etag.weak;
+
+        let tag = match etag.into_tag() {
+            Cow::Borrowed(tag) => Cow::Borrowed(tag.as_bytes()),
+            Cow::Owned(tag) => Cow::Owned(tag.into_bytes()),
+        };
+
+        EntityTagBytes {
+            weak,
+            tag,
+        }
+    }
+}
+
+/// Write a valid UTF-8 part of an opaque tag, escaping `\` as `\\`.
+fn write_escaped(f: &mut Formatter<'_>, s: &str) -> Result<(), fmt::Error> {
+    for (i, part) in s.split('\\').enumerate() {
+        if i > 0 {
+            f.write_str("\\\\")?;
+        }
+
+        f.write_str(part)?;
+    }
+
+    Ok(())
+}
+
+impl<'t> Display for EntityTagBytes<'t> {
+    /// Format this entity tag, escaping the octets which are not valid UTF-8 as `\xHH` and `\` as `\\`, so that distinct entity tags are never formatted the same. Use `to_header_bytes` to get the raw form.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+
+        f.write_char('"')?;
+
+        let mut s = self.tag.as_ref();
+
+        loop {
+            match core::str::from_utf8(s) {
+                Ok(valid) => {
+                    write_escaped(f, valid)?;
+
+                    break;
+                },
+                Err(error) => {
+                    let (valid, rest) = s.split_at(error.valid_up_to());
+
+                    // `valid` has been checked
+                    write_escaped(f, core::str::from_utf8(valid).map_err(|_| fmt::Error)?)?;
+
+                    let invalid_length = error.error_len().unwrap_or(rest.len());
+
+                    for b in &rest[..invalid_length] {
+                        write!(f, "\\x{:02X}", b)?;
+                    }
+
+                    s = &rest[invalid_length..];
+                },
+            }
+        }
+
+        f.write_char('"')
+    }
+}
@@ -3,6 +3,7 @@ use core::fmt::{self, Display, Formatter};
 use std::error::Error;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Possible errors of `EntityTag`.
 pub enum EntityTagError {
     MissingStartingDoubleQuote,
@@ -0,0 +1,188 @@
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use highway::HighwayHasher;
+
+#[cfg(feature = "sha2")]
+use crate::digest::DigestAlgorithm;
+use crate::{epoch::salt, EntityTag, TagEncoding};
+
+#[cfg(feature = "sha2")]
+#[derive(Clone)]
+enum DigestHasher {
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+}
+
+#[cfg(feature = "sha2")]
+impl DigestHasher {
+    #[inline]
+    fn new(algorithm: DigestAlgorithm) -> Self {
+        use sha2::Digest;
+
+        match algorithm {
+            DigestAlgorithm::Sha256 => DigestHasher::Sha256(sha2::Sha256::new()),
+            DigestAlgorithm::Sha384 => DigestHasher::Sha384(sha2::Sha384::new()),
+            DigestAlgorithm::Sha512 => DigestHasher::Sha512(sha2::Sha512::new()),
+        }
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        use sha2::Digest;
+
+        match self {
+            DigestHasher::Sha256(hasher) => hasher.update(data),
+            DigestHasher::Sha384(hasher) => hasher.update(data),
+            DigestHasher::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    #[inline]
+    fn finalize(self) -> (DigestAlgorithm, Vec<u8>) {
+        use sha2::Digest;
+
+        match self {
+            DigestHasher::Sha256(hasher) => (DigestAlgorithm::Sha256, hasher.finalize().to_vec()),
+            DigestHasher::Sha384(hasher) => (DigestAlgorithm::Sha384, hasher.finalize().to_vec()),
+            DigestHasher::Sha512(hasher) => (DigestAlgorithm::Sha512, hasher.finalize().to_vec()),
+        }
+    }
+}
+
+/// The version of the format of the exported hasher state.
+const STATE_VERSION: u8 = 1;
+
+/// The length of a checkpoint of `HighwayHasher`.
+const CHECKPOINT_LEN: usize = 164;
+
+/// A streaming hasher which computes the same strong entity tag as `EntityTag::from_data` without holding the whole data in memory.
+///
+/// ```rust
+/// use entity_tag::{EntityTag, EntityTagHasher};
+///
+/// let mut hasher = EntityTagHasher::new();
+///
+/// hasher.write(b"f");
+/// hasher.write(b"oo");
+///
+/// assert_eq!(EntityTag::from_data(b"foo"), hasher.finish());
+/// ```
+#[derive(Clone, Default)]
+pub struct EntityTagHasher {
+    hasher: HighwayHasher,
+    #[cfg(feature = "sha2")]
+    digest: Option<DigestHasher>,
+}
+
+impl EntityTagHasher {
+    /// Create a new hasher.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new hasher which also computes a digest of the given algorithm in the same pass, for the `Content-Digest` or `Repr-Digest` header field.
+    #[cfg(feature = "sha2")]
+    #[inline]
+    pub fn with_digest(algorithm: DigestAlgorithm) -> Self {
+        EntityTagHasher {
+            hasher: HighwayHasher::default(),
+            digest: Some(DigestHasher::new(algorithm)),
+        }
+    }
+
+    /// Feed data into the hasher.
+    #[inline]
+    pub fn write(&mut self, data: &[u8]) {
+        count!("entity_tag_hashed_bytes_total", data.len());
+
+        self.hasher.write(data);
+
+        #[cfg(feature = "sha2")]
+        if let Some(digest) = self.digest.as_mut() {
+            digest.update(data);
+        }
+    }
+
+    /// Feed all the remaining data of a buffer into the hasher chunk by chunk, and advance the buffer to its end. Non-contiguous buffers, such as chains of `Bytes`, do not need to be copied into a single slice first.
+    #[cfg(feature = "bytes")]
+    #[inline]
+    pub fn write_buf(&mut self, buf: &mut impl bytes::Buf) {
+        while buf.has_remaining() {
+            let chunk = buf.chunk();
+            let len = chunk.len();
+
+            self.write(chunk);
+
+            buf.advance(len);
+        }
+    }
+
+    /// Export the state of this hasher as a small opaque byte blob, so that hashing can be resumed later by `from_state`, even in another process (e.g. for chunked uploads spanning several requests). Returns `None` if the hasher also computes a digest, whose state cannot be exported.
+    pub fn to_state(&self) -> Option<Vec<u8>> {
+        #[cfg(feature = "sha2")]
+        if self.digest.is_some() {
+            return None;
+        }
+
+        let mut state = Vec::with_capacity(1 + CHECKPOINT_LEN);
+
+        state.push(STATE_VERSION);
+        state.extend_from_slice(&self.hasher.checkpoint());
+
+        Some(state)
+    }
+
+    /// Restore a hasher from the state exported by `to_state`. Returns `None` if the state is malformed.
+    pub fn from_state(state: &[u8]) -> Option<Self> {
+        let (&version, checkpoint) = state.split_first()?;
+
+        if version != STATE_VERSION || checkpoint.len() != CHECKPOINT_LEN {
+            return None;
+        }
+
+        let mut data = [0u8; CHECKPOINT_LEN];
+
+        data.copy_from_slice(checkpoint);
+
+        // the last 4 bytes are the length of the buffered data, which is less than a packet (32 bytes)
+        let mut buffered = [0u8; 4];
+
+        buffered.copy_from_slice(&data[CHECKPOINT_LEN - 4..]);
+
+        if u32::from_le_bytes(buffered) >= 32 {
+            return None;
+        }
+
+        let hasher = HighwayHasher::from_checkpoint(data);
+
+        Some(EntityTagHasher {
+            hasher,
+            #[cfg(feature = "sha2")]
+            digest: None,
+        })
+    }
+
+    /// Construct a strong EntityTag from the data fed so far.
+    #[inline]
+    pub fn finish(&self) -> EntityTag<'static> {
+        EntityTag::from_hash(false, salt(self.hasher.finish()))
+    }
+
+    /// Construct a strong EntityTag from the data fed so far, encoding the opaque tag with the given encoding.
+    #[inline]
+    pub fn finish_with_encoding(&self, encoding: TagEncoding) -> EntityTag<'static> {
+        EntityTag::from_hash_with_encoding(false, salt(self.hasher.finish()), encoding)
+    }
+
+    /// Construct a strong EntityTag from the data fed so far, together with the digest if the hasher was created by `with_digest`.
+    #[cfg(feature = "sha2")]
+    #[inline]
+    pub fn finish_with_digest(self) -> (EntityTag<'static>, Option<(DigestAlgorithm, Vec<u8>)>) {
+        let etag = self.finish();
+
+        (etag, self.digest.map(DigestHasher::finalize))
+    }
+}
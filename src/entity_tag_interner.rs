@@ -0,0 +1,115 @@
+use alloc::{collections::BTreeSet, sync::Arc};
+use core::{borrow::Borrow, cmp::Ordering};
+
+use crate::EntityTag;
+
+/// An interned entity tag, ordered by its opaque tag so that it can be looked up with a `str`.
+#[derive(Debug, Clone)]
+struct Interned(Arc<EntityTag<'static>>);
+
+impl Interned {
+    #[inline]
+    fn tag(&self) -> &str {
+        self.0.get_tag()
+    }
+}
+
+impl PartialEq for Interned {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.tag() == other.tag()
+    }
+}
+
+impl Eq for Interned {}
+
+impl PartialOrd for Interned {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interned {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tag().cmp(other.tag())
+    }
+}
+
+impl Borrow<str> for Interned {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.tag()
+    }
+}
+
+/// A pool which deduplicates identical entity tags behind shared references, for caches whose entries point at far fewer distinct validators than there are entries.
+#[derive(Debug, Clone, Default)]
+pub struct EntityTagInterner {
+    strong: BTreeSet<Interned>,
+    weak:   BTreeSet<Interned>,
+}
+
+impl EntityTagInterner {
+    /// Create an empty pool.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn set(&self, weak: bool) -> &BTreeSet<Interned> {
+        if weak {
+            &self.weak
+        } else {
+            &self.strong
+        }
+    }
+
+    /// Get the number of distinct entity tags in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.strong.len() + self.weak.len()
+    }
+
+    /// Whether the pool is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.strong.is_empty() && self.weak.is_empty()
+    }
+
+    /// Get the shared entity tag identical to `etag`, adding a copy of `etag` to the pool if there is none. The weakness indicator is taken into account.
+    pub fn intern(&mut self, etag: &EntityTag) -> Arc<EntityTag<'static>> {
+        if let Some(interned) = self.get(etag) {
+            return interned;
+        }
+
+        let interned = Arc::new(etag.clone().into_owned());
+
+        let set = if etag.weak { &mut self.weak } else { &mut self.strong };
+
+        set.insert(Interned(interned.clone()));
+
+        interned
+    }
+
+    /// Get the shared entity tag identical to `etag` if it is in the pool.
+    #[inline]
+    pub fn get(&self, etag: &EntityTag) -> Option<Arc<EntityTag<'static>>> {
+        self.set(etag.weak).get(etag.get_tag()).map(|interned| interned.0.clone())
+    }
+
+    /// Remove the entity tags which are no longer referenced outside the pool.
+    pub fn remove_unused(&mut self) {
+        self.strong.retain(|interned| Arc::strong_count(&interned.0) > 1);
+        self.weak.retain(|interned| Arc::strong_count(&interned.0) > 1);
+    }
+
+    /// Remove all entity tags from the pool. The shared entity tags which are still referenced stay alive.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.strong.clear();
+        self.weak.clear();
+    }
+}
@@ -0,0 +1,403 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{
+    EntityTag, EntityTagError, EntityTagListItem, EntityTagListIter, PreconditionError,
+    PreconditionFailure, PreconditionHeader, PreconditionStep,
+};
+
+/// The value of the `If-Match` or `If-None-Match` header field, defined in [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.1).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EntityTagList<'t> {
+    /// `*`
+    Any,
+    /// 1#entity-tag
+    Tags(Vec<EntityTag<'t>>),
+}
+
+#[inline]
+pub(crate) fn is_ows(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Parses the comma-separated entity tags of a field value one by one.
+#[derive(Debug, Clone)]
+pub(crate) struct TagsParser<'t> {
+    s:       &'t str,
+    lenient: bool,
+    started: bool,
+    done:    bool,
+}
+
+impl<'t> TagsParser<'t> {
+    #[inline]
+    pub(crate) fn new(s: &'t str) -> Self {
+        TagsParser {
+            s:       s.trim_matches(is_ows),
+            lenient: false,
+            started: false,
+            done:    false,
+        }
+    }
+
+    /// Create a parser which skips empty elements.
+    #[inline]
+    pub(crate) fn lenient(s: &'t str) -> Self {
+        TagsParser {
+            lenient: true,
+            ..Self::new(s)
+        }
+    }
+}
+
+impl<'t> Iterator for TagsParser<'t> {
+    type Item = Result<EntityTag<'t>, EntityTagError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.started {
+            if self.s.is_empty() {
+                self.done = true;
+
+                return None;
+            }
+
+            if let Some(s) = self.s.strip_prefix(',') {
+                self.s = s.trim_start_matches(is_ows);
+            } else {
+                self.done = true;
+
+                return Some(Err(EntityTagError::InvalidTag));
+            }
+        }
+
+        if self.lenient {
+            self.s = self.s.trim_start_matches(|c| c == ',' || is_ows(c));
+
+            if self.s.is_empty() {
+                self.done = true;
+
+                return None;
+            }
+        }
+
+        self.started = true;
+
+        match EntityTag::parse_prefix(self.s) {
+            Ok((etag, rest)) => {
+                self.s = rest.trim_start_matches(is_ows);
+
+                Some(Ok(etag))
+            },
+            Err(error) => {
+                self.done = true;
+
+                Some(Err(error))
+            },
+        }
+    }
+}
+
+impl<'t> EntityTagList<'t> {
+    /// Parse and construct a new EntityTagList from a `str`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: ?Sized + AsRef<str>>(list: &'t S) -> Result<Self, EntityTagError> {
+        let s = list.as_ref().trim_matches(is_ows);
+
+        if s == "*" {
+            return Ok(EntityTagList::Any);
+        }
+
+        Ok(EntityTagList::Tags(TagsParser::new(s).collect::<Result<Vec<_>, _>>()?))
+    }
+
+    /// Parse and construct a new EntityTagList from a `str`, skipping empty list elements (e.g. `"a", , "b",`) instead of failing. A value without any element results in an empty list.
+    pub fn from_str_lenient<S: ?Sized + AsRef<str>>(list: &'t S) -> Result<Self, EntityTagError> {
+        let s = list.as_ref().trim_matches(is_ows);
+
+        if s == "*" {
+            return Ok(EntityTagList::Any);
+        }
+
+        Ok(EntityTagList::Tags(TagsParser::lenient(s).collect::<Result<Vec<_>, _>>()?))
+    }
+
+    /// Lazily parse the values of several occurrences of the `If-Match` or `If-None-Match` header field, yielding the `*` and the entity tags in them one by one without collecting them.
+    #[inline]
+    pub fn iter_from_values<I: IntoIterator<Item = &'t str>>(
+        values: I,
+    ) -> EntityTagListIter<'t, I::IntoIter> {
+        EntityTagListIter::new(values.into_iter())
+    }
+
+    /// Parse the values of several occurrences of a header field and join them together. Returns `Ok(None)` if there is no value.
+    #[cfg(any(feature = "http", feature = "tonic", feature = "tide"))]
+    pub(crate) fn join_values<I: Iterator<Item = Result<&'t str, EntityTagError>>>(
+        mut values: I,
+    ) -> Result<Option<Self>, EntityTagError> {
+        let mut list = match values.next() {
+            Some(value) => EntityTagList::from_str(value?)?,
+            None => return Ok(None),
+        };
+
+        for value in values {
+            let other = EntityTagList::from_str(value?)?;
+
+            match (&mut list, other) {
+                (EntityTagList::Tags(tags), EntityTagList::Tags(other)) => tags.extend(other),
+                // `*` cannot be combined with anything else
+                _ => return Err(EntityTagError::InvalidTag),
+            }
+        }
+
+        Ok(Some(list))
+    }
+
+    /// Parse and construct a new EntityTagList from a `String`.
+    #[inline]
+    pub fn from_string<S: AsRef<str>>(list: S) -> Result<EntityTagList<'static>, EntityTagError> {
+        Ok(EntityTagList::from_str(list.as_ref())?.into_owned())
+    }
+}
+
+impl<'t> EntityTagList<'t> {
+    /// Parse and construct a new EntityTagList from a `String`, skipping empty list elements (e.g. `"a", , "b",`) instead of failing. A value without any element results in an empty list.
+    #[inline]
+    pub fn from_string_lenient<S: AsRef<str>>(
+        list: S,
+    ) -> Result<EntityTagList<'static>, EntityTagError> {
+        Ok(EntityTagList::from_str_lenient(list.as_ref())?.into_owned())
+    }
+
+    /// Whether this list is `*`.
+    #[inline]
+    pub const fn is_any(&self) -> bool {
+        matches!(self, EntityTagList::Any)
+    }
+
+    /// Whether this list contains an entity tag which is equivalent to `etag` under the strong comparison. `*` contains any entity tag.
+    #[inline]
+    pub fn contains_strong(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTagList::Any => true,
+            EntityTagList::Tags(tags) => tags.iter().any(|e| e.strong_eq(etag)),
+        }
+    }
+
+    /// Whether this list contains an entity tag which is equivalent to `etag` under the weak comparison. `*` contains any entity tag.
+    #[inline]
+    pub fn contains_weak(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTagList::Any => true,
+            EntityTagList::Tags(tags) => tags.iter().any(|e| e.weak_eq(etag)),
+        }
+    }
+
+    /// Remove redundant entity tags which have the same opaque tag as an earlier one, keeping the order of the first occurrences.
+    ///
+    /// If both the weak and the strong version of an opaque tag are present, the strong one is kept, because a weak entity tag never matches under the strong comparison (`If-Match`) and the weakness is ignored under the weak comparison (`If-None-Match`). Therefore the result of evaluating the list does not change.
+    pub fn dedup(&mut self) {
+        if let EntityTagList::Tags(tags) = self {
+            let mut deduped: Vec<EntityTag<'t>> = Vec::with_capacity(tags.len());
+
+            for etag in tags.drain(..) {
+                match deduped.iter_mut().find(|e| e.weak_eq(&etag)) {
+                    Some(e) => e.weak = e.weak && etag.weak,
+                    None => deduped.push(etag),
+                }
+            }
+
+            *tags = deduped;
+        }
+    }
+
+    /// Remove redundant entity tags like `dedup` does, and then sort the entity tags by their opaque tags, so that equivalent lists become identical.
+    pub fn normalize(&mut self) {
+        self.dedup();
+
+        if let EntityTagList::Tags(tags) = self {
+            tags.sort_unstable_by(|a, b| a.get_tag_cow().cmp(b.get_tag_cow()));
+        }
+    }
+
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> EntityTagList<'static> {
+        match self {
+            EntityTagList::Any => EntityTagList::Any,
+            EntityTagList::Tags(tags) => {
+                EntityTagList::Tags(tags.into_iter().map(EntityTag::into_owned).collect())
+            },
+        }
+    }
+}
+
+impl<'t> EntityTagList<'t> {
+    /// Evaluate this list as the `If-Match` precondition, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.1). `current` is the entity tag of the selected representation, or `None` if the target resource has no current representation.
+    ///
+    /// The strong comparison is used.
+    #[inline]
+    pub fn check_if_match(&self, current: Option<&EntityTag>) -> Result<(), PreconditionError> {
+        self.evaluate_if_match(current).0
+    }
+
+    /// Evaluate this list as the `If-Match` precondition like `check_if_match` does, and explain how it was evaluated.
+    #[inline]
+    pub fn trace_if_match(&self, current: Option<&EntityTag>) -> PreconditionStep {
+        let (outcome, matched) = self.evaluate_if_match(current);
+
+        PreconditionStep::new(
+            PreconditionHeader::IfMatch,
+            self.matched_item(current, matched),
+            outcome,
+        )
+    }
+
+    fn evaluate_if_match(
+        &self,
+        current: Option<&EntityTag>,
+    ) -> (Result<(), PreconditionError>, Option<&EntityTag<'t>>) {
+        let failure = match (self, current) {
+            (EntityTagList::Any, Some(_)) => {
+                debug!("If-Match: `*` matched the current representation");
+
+                return (Ok(()), None);
+            },
+            (EntityTagList::Any, None) => PreconditionFailure::NoRepresentation,
+            (EntityTagList::Tags(tags), Some(current)) => {
+                if let Some(etag) = tags.iter().find(|e| e.strong_eq(current)) {
+                    debug!(%current, "If-Match: an entity tag matched under the strong comparison");
+
+                    return (Ok(()), Some(etag));
+                }
+
+                PreconditionFailure::NoneMatched
+            },
+            (EntityTagList::Tags(_), None) => PreconditionFailure::NoneMatched,
+        };
+
+        debug!(
+            ?current,
+            list = %self,
+            %failure,
+            "If-Match: the precondition failed"
+        );
+
+        (Err(PreconditionError::new(PreconditionHeader::IfMatch, failure)), None)
+    }
+
+    /// Evaluate this list as the `If-None-Match` precondition, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.2). `current` is the entity tag of the selected representation, or `None` if the target resource has no current representation.
+    ///
+    /// The weak comparison is used.
+    #[inline]
+    pub fn check_if_none_match(
+        &self,
+        current: Option<&EntityTag>,
+    ) -> Result<(), PreconditionError> {
+        self.evaluate_if_none_match(current).0
+    }
+
+    /// Evaluate this list as the `If-None-Match` precondition like `check_if_none_match` does, and explain how it was evaluated.
+    #[inline]
+    pub fn trace_if_none_match(&self, current: Option<&EntityTag>) -> PreconditionStep {
+        let (outcome, matched) = self.evaluate_if_none_match(current);
+
+        PreconditionStep::new(
+            PreconditionHeader::IfNoneMatch,
+            self.matched_item(current, matched),
+            outcome,
+        )
+    }
+
+    fn evaluate_if_none_match(
+        &self,
+        current: Option<&EntityTag>,
+    ) -> (Result<(), PreconditionError>, Option<&EntityTag<'t>>) {
+        let (failure, matched) = match (self, current) {
+            (EntityTagList::Any, Some(_)) => (PreconditionFailure::RepresentationExists, None),
+            (EntityTagList::Tags(tags), Some(current)) => {
+                match tags.iter().find(|e| e.weak_eq(current)) {
+                    Some(etag) => (PreconditionFailure::Matched, Some(etag)),
+                    None => {
+                        debug!(%current, "If-None-Match: no entity tag matched under the weak comparison");
+
+                        return (Ok(()), None);
+                    },
+                }
+            },
+            (_, None) => return (Ok(()), None),
+        };
+
+        debug!(
+            ?current,
+            list = %self,
+            %failure,
+            "If-None-Match: the precondition failed"
+        );
+
+        (Err(PreconditionError::new(PreconditionHeader::IfNoneMatch, failure)), matched)
+    }
+
+    /// Get the element which matched the current entity tag. `*` matches any current representation.
+    #[inline]
+    fn matched_item(
+        &self,
+        current: Option<&EntityTag>,
+        matched: Option<&EntityTag<'t>>,
+    ) -> Option<EntityTagListItem<'static>> {
+        match (self, matched) {
+            (EntityTagList::Any, _) => current.map(|_| EntityTagListItem::Any),
+            (EntityTagList::Tags(_), matched) => {
+                matched.map(|etag| EntityTagListItem::Tag(etag.clone().into_owned()))
+            },
+        }
+    }
+}
+
+impl<'t> Display for EntityTagList<'t> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            EntityTagList::Any => f.write_str("*"),
+            EntityTagList::Tags(tags) => {
+                for (i, etag) in tags.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    // the formatter flags are meant for the whole list, not for each entity tag
+                    write!(f, "{}", etag)?;
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<'t> FromIterator<EntityTag<'t>> for Option<EntityTagList<'t>> {
+    /// Collect entity tags into a list. Returns `None` if there is no entity tag, because an empty list is not a valid value of the `If-Match` or `If-None-Match` header field.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = EntityTag<'t>>>(iter: I) -> Self {
+        let tags: Vec<EntityTag<'t>> = iter.into_iter().collect();
+
+        if tags.is_empty() {
+            None
+        } else {
+            Some(EntityTagList::Tags(tags))
+        }
+    }
+}
+
+impl<'t> Extend<EntityTag<'t>> for EntityTagList<'t> {
+    /// Append entity tags to the list. `*` absorbs them: it already matches any entity tag, so it stays unchanged and the given entity tags are dropped.
+    #[inline]
+    fn extend<I: IntoIterator<Item = EntityTag<'t>>>(&mut self, iter: I) {
+        if let EntityTagList::Tags(tags) = self {
+            tags.extend(iter);
+        }
+    }
+}
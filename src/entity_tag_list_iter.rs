@@ -0,0 +1,97 @@
+use crate::{
+    entity_tag_list::{is_ows, TagsParser},
+    EntityTag, EntityTagError,
+};
+
+/// An element of the `If-Match` or `If-None-Match` header field.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EntityTagListItem<'t> {
+    /// `*`
+    Any,
+    /// An entity tag.
+    Tag(EntityTag<'t>),
+}
+
+/// An iterator which lazily parses the values of several occurrences of the `If-Match` or `If-None-Match` header field. It is created by `EntityTagList::iter_from_values`.
+///
+/// `*` can only be the only element of the field, so a `*` mixed with entity tags results in an error. The iteration ends after the first error.
+#[derive(Debug, Clone)]
+pub struct EntityTagListIter<'t, I> {
+    values:  I,
+    current: Option<TagsParser<'t>>,
+    any:     bool,
+    tags:    bool,
+    done:    bool,
+}
+
+impl<'t, I> EntityTagListIter<'t, I> {
+    #[inline]
+    pub(crate) fn new(values: I) -> Self {
+        EntityTagListIter {
+            values,
+            current: None,
+            any: false,
+            tags: false,
+            done: false,
+        }
+    }
+
+    #[inline]
+    fn fail(
+        &mut self,
+        error: EntityTagError,
+    ) -> Option<Result<EntityTagListItem<'t>, EntityTagError>> {
+        self.done = true;
+
+        Some(Err(error))
+    }
+}
+
+impl<'t, I: Iterator<Item = &'t str>> Iterator for EntityTagListIter<'t, I> {
+    type Item = Result<EntityTagListItem<'t>, EntityTagError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            if let Some(parser) = self.current.as_mut() {
+                match parser.next() {
+                    Some(Ok(etag)) => {
+                        if self.any {
+                            return self.fail(EntityTagError::InvalidTag);
+                        }
+
+                        self.tags = true;
+
+                        return Some(Ok(EntityTagListItem::Tag(etag)));
+                    },
+                    Some(Err(error)) => return self.fail(error),
+                    None => self.current = None,
+                }
+            }
+
+            match self.values.next() {
+                Some(value) => {
+                    if value.trim_matches(is_ows) == "*" {
+                        if self.any || self.tags {
+                            return self.fail(EntityTagError::InvalidTag);
+                        }
+
+                        self.any = true;
+
+                        return Some(Ok(EntityTagListItem::Any));
+                    }
+
+                    self.current = Some(TagsParser::new(value));
+                },
+                None => {
+                    self.done = true;
+
+                    return None;
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{EntityTag, EntityTagList};
+
+/// A bidirectional mapping between the entity tags of an upstream server and the entity tags a reverse proxy sends downstream, e.g. after transforming the representations. The entity tags are looked up by their opaque tags.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct EntityTagMap {
+    downstream: BTreeMap<String, EntityTag<'static>>,
+    upstream:   BTreeMap<String, EntityTag<'static>>,
+}
+
+impl EntityTagMap {
+    /// Create an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of pairs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.downstream.len()
+    }
+
+    /// Whether there is no pair.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.downstream.is_empty()
+    }
+
+    /// Register a pair of an upstream entity tag and a downstream entity tag. The existing pairs which contain either of them are removed, so that the mapping stays one-to-one.
+    pub fn insert(&mut self, upstream: EntityTag, downstream: EntityTag) {
+        self.remove_upstream(&upstream);
+
+        if let Some(old) = self.upstream.remove(downstream.get_tag()) {
+            self.downstream.remove(old.get_tag());
+        }
+
+        let upstream = upstream.into_owned();
+        let downstream = downstream.into_owned();
+
+        self.downstream.insert(String::from(upstream.get_tag()), downstream.clone());
+        self.upstream.insert(String::from(downstream.get_tag()), upstream);
+    }
+
+    /// Remove the pair of an upstream entity tag and return its downstream entity tag.
+    pub fn remove_upstream(&mut self, upstream: &EntityTag) -> Option<EntityTag<'static>> {
+        let downstream = self.downstream.remove(upstream.get_tag())?;
+
+        self.upstream.remove(downstream.get_tag());
+
+        Some(downstream)
+    }
+
+    /// Translate the entity tag of an upstream response into the one sent downstream.
+    #[inline]
+    pub fn to_downstream(&self, upstream: &EntityTag) -> Option<&EntityTag<'static>> {
+        self.downstream.get(upstream.get_tag())
+    }
+
+    /// Translate a downstream entity tag into the upstream one.
+    #[inline]
+    pub fn to_upstream(&self, downstream: &EntityTag) -> Option<&EntityTag<'static>> {
+        self.upstream.get(downstream.get_tag())
+    }
+
+    /// Translate the `If-None-Match` field value of a downstream request into the one sent upstream. Unknown entity tags are dropped because they cannot match any upstream representation. Returns `None` if no entity tag is left, in which case the request should be forwarded without the header field.
+    #[inline]
+    pub fn translate_if_none_match(&self, list: &EntityTagList) -> Option<EntityTagList<'static>> {
+        translate(list, |downstream| self.to_upstream(downstream))
+    }
+
+    /// Translate the `If-Match` field value of a downstream request into the one sent upstream. Weak entity tags and unknown entity tags are dropped because they cannot pass the strong comparison. Returns `None` if no entity tag is left, in which case the precondition fails unless the list is `*`.
+    pub fn translate_if_match(&self, list: &EntityTagList) -> Option<EntityTagList<'static>> {
+        translate(list, |downstream| {
+            if downstream.weak {
+                return None;
+            }
+
+            let upstream = self.to_upstream(downstream)?;
+
+            // the registered downstream entity tag has to be strong as well
+            if self.to_downstream(upstream)?.weak {
+                None
+            } else {
+                Some(upstream)
+            }
+        })
+    }
+}
+
+fn translate<'a, F: Fn(&EntityTag) -> Option<&'a EntityTag<'static>>>(
+    list: &EntityTagList,
+    f: F,
+) -> Option<EntityTagList<'static>> {
+    match list {
+        EntityTagList::Any => Some(EntityTagList::Any),
+        EntityTagList::Tags(tags) => {
+            let tags: Vec<EntityTag<'static>> = tags.iter().filter_map(f).cloned().collect();
+
+            if tags.is_empty() {
+                None
+            } else {
+                Some(EntityTagList::Tags(tags))
+            }
+        },
+    }
+}
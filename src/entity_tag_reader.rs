@@ -0,0 +1,71 @@
+use std::io::{self, Read};
+
+use crate::{EntityTag, EntityTagHasher};
+
+/// A reader which hashes all bytes read through it from an inner reader, so that the strong entity tag of an uploaded body is obtained as a byproduct of consuming it.
+///
+/// The entity tag is the same as the one computed by `EntityTag::from_data` over the read bytes.
+pub struct EntityTagReader<R> {
+    inner:  R,
+    hasher: EntityTagHasher,
+}
+
+impl<R: Read> EntityTagReader<R> {
+    /// Wrap a reader.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self::with_hasher(inner, EntityTagHasher::new())
+    }
+
+    /// Wrap a reader with a hasher, such as one created by `EntityTagHasher::with_digest`.
+    #[inline]
+    pub fn with_hasher(inner: R, hasher: EntityTagHasher) -> Self {
+        EntityTagReader {
+            inner,
+            hasher,
+        }
+    }
+
+    /// Get a reference to the inner reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner reader. Bytes read directly from it are not hashed.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Construct a strong EntityTag from the bytes read so far.
+    #[inline]
+    pub fn etag(&self) -> EntityTag<'static> {
+        self.hasher.finish()
+    }
+
+    /// Unwrap the inner reader, returning it together with the strong EntityTag of all the read bytes.
+    #[inline]
+    pub fn finish(self) -> (R, EntityTag<'static>) {
+        let etag = self.hasher.finish();
+
+        (self.inner, etag)
+    }
+
+    /// Unwrap the inner reader and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (R, EntityTagHasher) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<R: Read> Read for EntityTagReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+
+        self.hasher.write(&buf[..n]);
+
+        Ok(n)
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+use crate::EntityTag;
+use crate::{buffer, EntityTagError, ParseOptions};
+
+/// A borrowed entity tag, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3), which can be parsed, compared and formatted without allocating. It is available without the `alloc` feature.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EntityTagRef<'t> {
+    /// Whether to have a weakness indicator.
+    pub weak:       bool,
+    /// *etagc
+    pub(crate) tag: &'t str,
+}
+
+impl<'t> EntityTagRef<'t> {
+    #[inline]
+    pub(crate) fn check_unquoted_bytes(s: &[u8]) -> Result<(), EntityTagError> {
+        if s.iter().all(|&c| c == b'\x21' || (b'\x23'..=b'\x7e').contains(&c) || c >= b'\x80') {
+            Ok(())
+        } else {
+            Err(EntityTagError::InvalidTag)
+        }
+    }
+
+    #[inline]
+    pub(crate) fn check_unquoted_tag(s: &str) -> Result<(), EntityTagError> {
+        Self::check_unquoted_bytes(s.as_bytes())
+    }
+
+    /// Validate a tag whose double quotes are optional, and slice the opaque tag out of it without the double quotes.
+    pub(crate) fn unquote_tag(s: &str) -> Result<&str, EntityTagError> {
+        let s = match s.strip_prefix('"') {
+            Some(s) => s.strip_suffix('"').ok_or(EntityTagError::MissingClosingDoubleQuote)?,
+            None => s,
+        };
+
+        // now check the ETag characters
+        Self::check_unquoted_tag(s)?;
+
+        Ok(s)
+    }
+
+    /// Validate a tag whose double quotes are required, and slice the opaque tag out of it without the double quotes.
+    #[inline]
+    pub(crate) fn unquote_opaque_tag(s: &str) -> Result<&str, EntityTagError> {
+        let s = s.strip_prefix('"').ok_or(EntityTagError::MissingStartingDoubleQuote)?;
+        let s = s.strip_suffix('"').ok_or(EntityTagError::MissingClosingDoubleQuote)?;
+
+        // now check the ETag characters
+        Self::check_unquoted_tag(s)?;
+
+        Ok(s)
+    }
+}
+
+impl<'t> EntityTagRef<'t> {
+    /// Construct a new EntityTagRef without checking.
+    #[allow(clippy::missing_safety_doc)]
+    #[inline]
+    pub const unsafe fn new_unchecked(weak: bool, tag: &'t str) -> Self {
+        EntityTagRef {
+            weak,
+            tag,
+        }
+    }
+
+    /// Construct a new EntityTagRef. The double quotes of `tag` are optional.
+    #[inline]
+    pub fn with_str<S: ?Sized + AsRef<str>>(
+        weak: bool,
+        tag: &'t S,
+    ) -> Result<Self, EntityTagError> {
+        Ok(EntityTagRef {
+            weak,
+            tag: Self::unquote_tag(tag.as_ref())?,
+        })
+    }
+
+    /// Parse and construct a new EntityTagRef from a `str`.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    pub fn from_str<S: ?Sized + AsRef<str>>(etag: &'t S) -> Result<Self, EntityTagError> {
+        Self::from_str_with(etag, ParseOptions::new())
+    }
+
+    /// Parse and construct a new EntityTagRef from a `str` with relaxations of the grammar.
+    pub fn from_str_with<S: ?Sized + AsRef<str>>(
+        etag: &'t S,
+        options: ParseOptions,
+    ) -> Result<Self, EntityTagError> {
+        let (weak, opaque_tag) = options.strip_weak(etag.as_ref());
+
+        let tag = if options.missing_quotes && !opaque_tag.starts_with('"') {
+            Self::check_unquoted_tag(opaque_tag)?;
+
+            opaque_tag
+        } else {
+            Self::unquote_opaque_tag(opaque_tag)?
+        };
+
+        Ok(EntityTagRef {
+            weak,
+            tag,
+        })
+    }
+
+    /// Parse an entity tag at the beginning of `s` in place, returning it together with the remaining part of `s`. This can be used to walk through a comma-separated list of entity tags, such as the value of the `If-None-Match` header field, without allocating.
+    pub fn parse_prefix<S: ?Sized + AsRef<str>>(
+        s: &'t S,
+    ) -> Result<(Self, &'t str), EntityTagError> {
+        let s = s.as_ref();
+
+        let (weak, s) = if let Some(s) = s.strip_prefix("W/") { (true, s) } else { (false, s) };
+
+        let s = s.strip_prefix('"').ok_or(EntityTagError::MissingStartingDoubleQuote)?;
+
+        let end = s.find('"').ok_or(EntityTagError::MissingClosingDoubleQuote)?;
+
+        let tag = &s[..end];
+
+        Self::check_unquoted_tag(tag)?;
+
+        Ok((
+            EntityTagRef {
+                weak,
+                tag,
+            },
+            &s[(end + 1)..],
+        ))
+    }
+
+    /// Get the tag. The double quotes are not included.
+    #[inline]
+    pub const fn get_tag(&self) -> &'t str {
+        self.tag
+    }
+}
+
+impl<'t> EntityTagRef<'t> {
+    /// For strong comparison two entity-tags are equivalent if both are not weak and their opaque-tags match character-by-character.
+    #[inline]
+    pub fn strong_eq(&self, other: &EntityTagRef) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// For weak comparison two entity-tags are equivalent if their opaque-tags match character-by-character, regardless of either or both being tagged as "weak".
+    #[inline]
+    pub fn weak_eq(&self, other: &EntityTagRef) -> bool {
+        self.tag == other.tag
+    }
+
+    /// The inverse of `strong_eq`.
+    #[inline]
+    pub fn strong_ne(&self, other: &EntityTagRef) -> bool {
+        !self.strong_eq(other)
+    }
+
+    /// The inverse of `weak_eq`.
+    #[inline]
+    pub fn weak_ne(&self, other: &EntityTagRef) -> bool {
+        !self.weak_eq(other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t> EntityTag<'t> {
+    /// Borrow this entity tag as an `EntityTagRef`.
+    #[inline]
+    pub fn as_entity_tag_ref(&self) -> EntityTagRef<'_> {
+        EntityTagRef {
+            weak: self.weak, tag: self.get_tag_cow()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t> From<EntityTagRef<'t>> for EntityTag<'t> {
+    #[inline]
+    fn from(etag: EntityTagRef<'t>) -> Self {
+        EntityTag {
+            weak: etag.weak, tag: Cow::Borrowed(etag.tag)
+        }
+    }
+}
+
+impl<'t> Display for EntityTagRef<'t> {
+    /// Format this entity tag like `EntityTag` does, including the escaping alternate form.
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        buffer::fmt_entity_tag(self.weak, self.tag, f)
+    }
+}
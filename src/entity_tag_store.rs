@@ -0,0 +1,252 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{mem, num::NonZeroUsize};
+#[cfg(feature = "std")]
+use std::{ffi::OsString, fs, io, path::Path};
+
+use crate::{
+    binary::{read_length, write_length},
+    BinaryDecodeError, EntityTag,
+};
+
+/// The beginning of the binary form of a store, whose last byte is the version of the format.
+const HEADER: &[u8] = b"ETS\x01";
+
+fn encode_entry(bytes: &mut Vec<u8>, key: &str, etag: &EntityTag) {
+    write_length(bytes, key.len());
+
+    bytes.extend_from_slice(key.as_bytes());
+    bytes.extend_from_slice(&etag.to_bytes());
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    etag: EntityTag<'static>,
+    /// The time the entry was last used, which orders the entries of a bounded store.
+    tick: u64,
+}
+
+/// A registry of the current entity tags of resources, keyed by e.g. their URLs or file paths, so that a server can evaluate preconditions without loading the resources.
+///
+/// A store created by `bounded` holds at most the given number of entries and evicts the least recently used one when it is full, so keys controlled by clients cannot make it grow without limit.
+#[derive(Debug, Clone, Default)]
+pub struct EntityTagStore {
+    entries:  BTreeMap<String, Entry>,
+    /// The keys of a bounded store ordered by the time they were last used.
+    recency:  BTreeMap<u64, String>,
+    tick:     u64,
+    capacity: Option<NonZeroUsize>,
+}
+
+impl EntityTagStore {
+    /// Create an empty store without a limit.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty store which holds at most `capacity` entries, evicting the least recently used entry when a new key is inserted into a full store.
+    #[inline]
+    pub fn bounded(capacity: NonZeroUsize) -> Self {
+        EntityTagStore {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Get the maximum number of entries. Returns `None` if the store is not bounded.
+    #[inline]
+    pub const fn capacity(&self) -> Option<NonZeroUsize> {
+        self.capacity
+    }
+
+    /// Get the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there is no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+
+        self.tick
+    }
+
+    /// Get the entity tag of a key and mark the entry as the most recently used one.
+    pub fn get(&mut self, key: &str) -> Option<&EntityTag<'static>> {
+        let tick = self.next_tick();
+
+        let entry = self.entries.get_mut(key)?;
+
+        if self.capacity.is_some() {
+            if let Some(key) = self.recency.remove(&entry.tick) {
+                self.recency.insert(tick, key);
+            }
+
+            entry.tick = tick;
+        }
+
+        count!("entity_tag_cache_hits_total", 1, "cache" => "store");
+
+        Some(&entry.etag)
+    }
+
+    /// Get the entity tag of a key without changing the order of eviction.
+    #[inline]
+    pub fn peek(&self, key: &str) -> Option<&EntityTag<'static>> {
+        self.entries.get(key).map(|entry| &entry.etag)
+    }
+
+    /// Whether the store has an entity tag for the key. The order of eviction is not changed.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Set the entity tag of a key and mark the entry as the most recently used one. Returns the previous entity tag of the key, if any.
+    ///
+    /// If the store is bounded and full, the least recently used entry is evicted to make room for a new key.
+    pub fn insert<K: Into<String>>(
+        &mut self,
+        key: K,
+        etag: EntityTag,
+    ) -> Option<EntityTag<'static>> {
+        let key = key.into();
+        let etag = etag.into_owned();
+        let tick = self.next_tick();
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            if self.capacity.is_some() {
+                self.recency.remove(&entry.tick);
+                self.recency.insert(tick, key);
+
+                entry.tick = tick;
+            }
+
+            return Some(mem::replace(&mut entry.etag, etag));
+        }
+
+        if let Some(capacity) = self.capacity {
+            if self.entries.len() >= capacity.get() {
+                self.evict();
+            }
+
+            self.recency.insert(tick, key.clone());
+        }
+
+        self.entries.insert(key, Entry {
+            etag,
+            tick,
+        });
+
+        None
+    }
+
+    /// Evict the least recently used entry.
+    fn evict(&mut self) {
+        let oldest = self.recency.keys().next().copied();
+
+        if let Some(key) = oldest.and_then(|tick| self.recency.remove(&tick)) {
+            debug!(%key, "evicted the least recently used entity tag");
+
+            self.entries.remove(&key);
+        }
+    }
+
+    /// Remove the entity tag of a key, e.g. after the resource is deleted.
+    pub fn remove(&mut self, key: &str) -> Option<EntityTag<'static>> {
+        let entry = self.entries.remove(key)?;
+
+        self.recency.remove(&entry.tick);
+
+        Some(entry.etag)
+    }
+
+    /// Remove all the entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Encode the entries into a compact binary form, which consists of a header and, for every entry, the length of the key as an unsigned LEB128 integer, the key and the entity tag encoded by `EntityTag::to_bytes`.
+    ///
+    /// The entries of a bounded store are encoded from the least recently used one, so that decoding them into a bounded store restores the order of eviction.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(HEADER);
+
+        if self.capacity.is_some() {
+            for key in self.recency.values() {
+                encode_entry(&mut bytes, key, &self.entries[key.as_str()].etag);
+            }
+        } else {
+            for (key, entry) in self.entries.iter() {
+                encode_entry(&mut bytes, key, &entry.etag);
+            }
+        }
+
+        bytes
+    }
+
+    /// Insert the entries decoded from the binary form produced by `to_bytes`. Nothing is inserted if the data is malformed.
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) -> Result<(), BinaryDecodeError> {
+        let mut bytes = bytes.strip_prefix(HEADER).ok_or(BinaryDecodeError::InvalidHeader)?;
+
+        let mut entries = Vec::new();
+
+        while !bytes.is_empty() {
+            let (length, n) = read_length(bytes)?;
+
+            let end = n.checked_add(length).ok_or(BinaryDecodeError::InvalidLength)?;
+
+            let key = bytes.get(n..end).ok_or(BinaryDecodeError::UnexpectedEnd)?;
+            let key = core::str::from_utf8(key).map_err(|_| BinaryDecodeError::InvalidKey)?;
+
+            let (etag, n) = EntityTag::from_bytes_encoded(&bytes[end..])?;
+
+            entries.push((key, etag));
+
+            bytes = &bytes[end + n..];
+        }
+
+        for (key, etag) in entries {
+            self.insert(key, etag);
+        }
+
+        Ok(())
+    }
+
+    /// Write the entries to a file in the binary form produced by `to_bytes`, e.g. before the process exits, so that the entity tags do not need to be computed again after a restart.
+    ///
+    /// The data is written to a temporary file next to `path` first, which is then renamed to `path`, so that the file is never left half-written.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let mut temp = OsString::from(path);
+        temp.push(".tmp");
+
+        fs::write(&temp, self.to_bytes())?;
+        fs::rename(&temp, path)
+    }
+
+    /// Create an unbounded store from a file written by `save`. To load the entries into a bounded store, pass the content of the file to `extend_from_bytes` instead.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+
+        let mut store = Self::new();
+
+        store
+            .extend_from_bytes(&bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        Ok(store)
+    }
+}
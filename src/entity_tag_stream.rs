@@ -0,0 +1,175 @@
+use alloc::string::{String, ToString};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{EntityTag, EntityTagHasher};
+
+/// An adapter which passes the chunks of a stream or a body through while hashing them, so that the strong entity tag is computed as the data flows through an async pipeline.
+///
+/// It implements `futures_core::Stream` for a stream of `Result<B, E>` where `B: AsRef<[u8]>`, such as `Bytes`, with the `futures-core` feature, and `http_body::Body` with the `body` feature. The inner stream or body needs to be `Unpin`; wrap it with `Box::pin` otherwise.
+///
+/// The entity tag is the same as the one computed by `EntityTag::from_data` over the concatenated chunks.
+///
+/// For chunked responses whose body cannot be buffered to set the `ETag` header field up front, the entity tag can be sent as a trailer field once the body ends. See `trailer_field` and `with_trailer`.
+pub struct EntityTagStream<S> {
+    inner:   S,
+    hasher:  EntityTagHasher,
+    #[cfg(all(feature = "http", feature = "http-body", feature = "bytes"))]
+    trailer: bool,
+}
+
+impl<S> EntityTagStream<S> {
+    /// Wrap a stream or a body.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self::with_hasher(inner, EntityTagHasher::new())
+    }
+
+    /// Wrap a stream or a body with a hasher, such as one created by `EntityTagHasher::with_digest`.
+    #[inline]
+    pub fn with_hasher(inner: S, hasher: EntityTagHasher) -> Self {
+        EntityTagStream {
+            inner,
+            hasher,
+            #[cfg(all(feature = "http", feature = "http-body", feature = "bytes"))]
+            trailer: false,
+        }
+    }
+
+    /// Make the body send the entity tag as the `ETag` trailer field after the data, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-6.5). If the inner body has its own trailer fields, the `ETag` field is added to them.
+    ///
+    /// The response should announce it with the `Trailer: ETag` header field. Note that recipients are allowed to discard trailer fields.
+    #[cfg(all(feature = "http", feature = "http-body", feature = "bytes"))]
+    #[inline]
+    pub fn with_trailer(mut self) -> Self {
+        self.trailer = true;
+
+        self
+    }
+
+    /// Get a reference to the inner stream or body.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Construct a strong EntityTag from the chunks passed through so far.
+    #[inline]
+    pub fn etag(&self) -> EntityTag<'static> {
+        self.hasher.finish()
+    }
+
+    /// Get the name and the value of the `ETag` trailer field for the chunks passed through so far. It should be taken after the stream or the body ends.
+    #[inline]
+    pub fn trailer_field(&self) -> (&'static str, String) {
+        ("ETag", self.etag().to_string())
+    }
+
+    /// Unwrap the inner stream or body and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (S, EntityTagHasher) {
+        (self.inner, self.hasher)
+    }
+}
+
+#[cfg(feature = "futures-core")]
+impl<S, B, E> futures_core::Stream for EntityTagStream<S>
+where
+    S: futures_core::Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = Result<B, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let poll = Pin::new(&mut this.inner).poll_next(cx);
+
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            this.hasher.write(chunk.as_ref());
+        }
+
+        poll
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(all(feature = "http-body", feature = "bytes"))]
+impl<S> http_body::Body for EntityTagStream<S>
+where
+    S: http_body::Body + Unpin,
+{
+    type Data = bytes::Bytes;
+    type Error = S::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        use bytes::Buf;
+
+        let this = self.get_mut();
+
+        match Pin::new(&mut this.inner).poll_frame(cx) {
+            Poll::Ready(Some(Ok(frame))) => {
+                // the data is flattened into `Bytes`, which is zero-copy if it already is `Bytes`
+                let frame = frame.map_data(|mut data| data.copy_to_bytes(data.remaining()));
+
+                if let Some(data) = frame.data_ref() {
+                    this.hasher.write(data);
+                }
+
+                #[cfg(feature = "http")]
+                let frame = match frame.into_trailers() {
+                    Ok(mut trailers) => {
+                        if this.trailer {
+                            this.trailer = false;
+
+                            trailers
+                                .insert(http::header::ETAG, http::HeaderValue::from(this.etag()));
+                        }
+
+                        http_body::Frame::trailers(trailers)
+                    },
+                    Err(frame) => frame,
+                };
+
+                Poll::Ready(Some(Ok(frame)))
+            },
+            Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+            #[cfg(feature = "http")]
+            Poll::Ready(None) if this.trailer => {
+                this.trailer = false;
+
+                let mut trailers = http::HeaderMap::new();
+
+                trailers.insert(http::header::ETAG, http::HeaderValue::from(this.etag()));
+
+                Poll::Ready(Some(Ok(http_body::Frame::trailers(trailers))))
+            },
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        #[cfg(feature = "http")]
+        if self.trailer {
+            return false;
+        }
+
+        self.inner.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "tokio")]
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::io::{self, Write};
+
+use crate::{EntityTag, EntityTagHasher};
+
+/// A writer which forwards all bytes to an inner writer while hashing them, so that the strong entity tag of a response can be computed as it is streamed to a file or a socket, without another pass over the data.
+///
+/// The entity tag is the same as the one computed by `EntityTag::from_data` over the written bytes. With the `tokio` feature, it also implements `tokio::io::AsyncWrite` if the inner writer does, so that files written with `tokio::fs` get their validator in the same pass.
+pub struct EntityTagWriter<W> {
+    inner:  W,
+    hasher: EntityTagHasher,
+}
+
+impl<W> EntityTagWriter<W> {
+    /// Wrap a writer.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_hasher(inner, EntityTagHasher::new())
+    }
+
+    /// Wrap a writer with a hasher, such as one created by `EntityTagHasher::with_digest`.
+    #[inline]
+    pub fn with_hasher(inner: W, hasher: EntityTagHasher) -> Self {
+        EntityTagWriter {
+            inner,
+            hasher,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner writer. Bytes written directly to it are not hashed.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Construct a strong EntityTag from the bytes written so far.
+    #[inline]
+    pub fn etag(&self) -> EntityTag<'static> {
+        self.hasher.finish()
+    }
+
+    /// Unwrap the inner writer, returning it together with the strong EntityTag of all the written bytes.
+    #[inline]
+    pub fn finish(self) -> (W, EntityTag<'static>) {
+        let etag = self.hasher.finish();
+
+        (self.inner, etag)
+    }
+
+    /// Unwrap the inner writer and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (W, EntityTagHasher) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<W: Write> Write for EntityTagWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+
+        // only the bytes accepted by the inner writer are hashed
+        self.hasher.write(&buf[..n]);
+
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin> tokio::io::AsyncWrite for EntityTagWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+
+        if let Poll::Ready(Ok(n)) = poll {
+            this.hasher.write(&buf[..n]);
+        }
+
+        poll
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
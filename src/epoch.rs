@@ -0,0 +1,57 @@
+use core::{
+    hash::Hasher,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use highway::HighwayHasher;
+
+static EPOCH: AtomicU32 = AtomicU32::new(0);
+
+/// Get the epoch mixed into the generated entity tags of this process. It is `0` unless changed by `set_epoch` or `bump_epoch`.
+#[inline]
+pub fn epoch() -> u32 {
+    EPOCH.load(Ordering::Relaxed)
+}
+
+/// Set the epoch mixed into the entity tags generated by hashing, such as the ones from `EntityTag::from_data`, `EntityTag::from_file_meta` and `EntityTagHasher`, so that every entity tag issued before stops matching and all the caches of clients are invalidated at once, e.g. after a bad deploy served corrupted bodies with valid entity tags.
+///
+/// The epoch is global to the process. Servers sharing validators must be configured with the same epoch. The epoch `0` keeps the entity tags the same as they are without an epoch. The entity tags cached in sidecar files, extended attributes and `FileTagCache` are recorded with the epoch, so they are computed again after it changes. Entity tags which are not generated by hashing, or which must match the ones computed by other software, are not affected. They are the ones from `EntityTag::from_hash`, `EntityTag::from_hash_with_encoding`, `EntityTag::from_components`, `EntityTag::from_data_md5`, `EntityTag::from_git_blob`, `EntityTag::from_git_blob_hash`, `EntityTag::from_rails_cache_key`, `EntityTag::from_digest`, `EntityTag::from_digest_field`, `EntityTag::from_sri`, `EntityTag::from_file_meta_actix_files` and `RowVersionTag`.
+#[inline]
+pub fn set_epoch(epoch: u32) {
+    EPOCH.store(epoch, Ordering::Relaxed);
+}
+
+/// Increase the epoch by one, which invalidates every entity tag generated before, and return the new epoch. See `set_epoch`.
+///
+/// After `u32::MAX`, the epoch wraps around to `1`, skipping `0`, which would bring back the entity tags generated without an epoch.
+pub fn bump_epoch() -> u32 {
+    let mut current = EPOCH.load(Ordering::Relaxed);
+
+    loop {
+        let next = match current.wrapping_add(1) {
+            0 => 1,
+            next => next,
+        };
+
+        match EPOCH.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return next,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// Mix the current epoch into a hash value computed from data.
+pub(crate) fn salt(hash: u64) -> u64 {
+    match epoch() {
+        0 => hash,
+        epoch => {
+            let mut hasher = HighwayHasher::default();
+
+            hasher.write(b"epoch");
+            hasher.write(&epoch.to_le_bytes());
+            hasher.write(&hash.to_le_bytes());
+
+            hasher.finish()
+        },
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    path::{Path, PathBuf},
+    sync::{Condvar, Mutex, MutexGuard},
+};
+
+use crate::{
+    epoch,
+    sidecar::{hash_file, snapshot},
+    EntityTag,
+};
+
+#[derive(Debug, Default)]
+struct State {
+    /// The entity tags of files with the snapshots of the metadata and the epoch they were computed from.
+    entries:   BTreeMap<PathBuf, (String, EntityTag<'static>)>,
+    /// The files being hashed.
+    in_flight: BTreeSet<PathBuf>,
+}
+
+/// An in-memory cache of the strong entity tags of files, as `from_data` computes, which are computed again when the length or the modified time of a file, or the epoch, changes.
+///
+/// Concurrent lookups of a file which is not cached share a single hashing pass. The first caller hashes the file and the others wait for its result, so a burst of requests for a large file does not read it many times.
+#[derive(Debug, Default)]
+pub struct FileTagCache {
+    state:    Mutex<State>,
+    finished: Condvar,
+}
+
+/// Removes a file from the in-flight set and wakes up the waiting callers, even if hashing panics.
+struct Flight<'a> {
+    cache: &'a FileTagCache,
+    path:  &'a Path,
+}
+
+impl<'a> Drop for Flight<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.cache.lock().in_flight.remove(self.path);
+
+        self.cache.finished.notify_all();
+    }
+}
+
+impl FileTagCache {
+    /// Create an empty cache.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // the state is consistent whenever the lock is released, even by a panic
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Get the number of cached entity tags.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether there is no cached entity tag.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Get the entity tag of a file. The cached entity tag is used if it is current. Otherwise, the file is hashed, unless another thread is already hashing it, in which case this waits for its result.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> io::Result<EntityTag<'static>> {
+        let path = path.as_ref();
+
+        loop {
+            let current = snapshot(&path.metadata()?, epoch());
+
+            let mut state = self.lock();
+
+            if let Some((cached, etag)) = state.entries.get(path) {
+                if *cached == current {
+                    count!("entity_tag_cache_hits_total", 1, "cache" => "file");
+
+                    return Ok(etag.clone());
+                }
+            }
+
+            if !state.in_flight.contains(path) {
+                state.in_flight.insert(path.to_path_buf());
+
+                break;
+            }
+
+            // check the cache again after the other thread finishes
+            drop(self.finished.wait(state).unwrap_or_else(|error| error.into_inner()));
+        }
+
+        let flight = Flight {
+            cache: self,
+            path,
+        };
+
+        // an epoch changed during hashing makes the entry stale rather than the entity tag outdated
+        let epoch = epoch();
+
+        let (etag, metadata) = hash_file(path)?;
+
+        self.lock().entries.insert(path.to_path_buf(), (snapshot(&metadata, epoch), etag.clone()));
+
+        drop(flight);
+
+        Ok(etag)
+    }
+
+    /// Remove the cached entity tag of a file, if any.
+    #[inline]
+    pub fn remove<P: AsRef<Path>>(&self, path: P) {
+        self.lock().entries.remove(path.as_ref());
+    }
+
+    /// Remove all the cached entity tags.
+    #[inline]
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+}
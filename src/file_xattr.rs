@@ -0,0 +1,54 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    sidecar::{decode_record, encode_record, hash_file},
+    EntityTag,
+};
+
+impl<'t> EntityTag<'t> {
+    /// The name of the extended attribute which stores the entity tag of a file.
+    pub const XATTR_NAME: &'static str = "user.etag";
+
+    /// Read the entity tag stored in the extended attribute of a file. Returns `Ok(None)` if the attribute does not exist, is malformed, or is stale, which means the length or the modified time of the file, or the epoch, has changed since the entity tag was computed.
+    pub fn read_xattr<P: AsRef<Path>>(path: P) -> io::Result<Option<EntityTag<'static>>> {
+        let path = path.as_ref();
+
+        let value = match xattr::get(path, Self::XATTR_NAME)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let metadata = fs::metadata(path)?;
+
+        Ok(core::str::from_utf8(&value).ok().and_then(|record| decode_record(record, &metadata)))
+    }
+
+    /// Write this entity tag into the extended attribute of a file, together with the current epoch and the length and the modified time in `metadata`, which should be taken before the entity tag was computed, so that a file modified during the computation makes the attribute stale.
+    #[inline]
+    pub fn write_xattr<P: AsRef<Path>>(&self, path: P, metadata: &fs::Metadata) -> io::Result<()> {
+        xattr::set(path, Self::XATTR_NAME, encode_record(self, metadata).as_bytes())
+    }
+
+    /// Get the strong entity tag of the content of a file, as `from_data` computes. The entity tag stored in the extended attribute is used if it is current, so the file does not need to be hashed. Otherwise, the file is hashed and the attribute is rewritten.
+    ///
+    /// The attribute is only a cache, so failing to read or write it (e.g. on file systems without extended attributes) does not cause an error.
+    pub fn from_path_with_xattr<P: AsRef<Path>>(path: P) -> io::Result<EntityTag<'static>> {
+        let path = path.as_ref();
+
+        if let Ok(Some(etag)) = Self::read_xattr(path) {
+            if !etag.weak {
+                count!("entity_tag_cache_hits_total", 1, "cache" => "xattr");
+
+                return Ok(etag);
+            }
+        }
+
+        let (etag, metadata) = hash_file(path)?;
+
+        if let Err(_error) = etag.write_xattr(path, &metadata) {
+            debug!(path = %path.display(), error = %_error, "cannot write the extended attribute");
+        }
+
+        Ok(etag)
+    }
+}
@@ -0,0 +1,122 @@
+//! Structured inputs for fuzzing with [arbitrary](https://docs.rs/arbitrary).
+//!
+//! `EntityTag` and `EntityTagList` implement `Arbitrary` by generating valid values only, including empty and obs-text opaque tags. Use `EntityTagInput` to also exercise the rejection of malformed field values.
+
+use alloc::{borrow::Cow, string::String, vec};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{EntityTag, EntityTagList};
+
+fn arbitrary_etagc(u: &mut Unstructured<'_>) -> Result<char> {
+    if u.ratio(1u8, 8)? {
+        // obs-text
+        Ok(char::from_u32(u.int_in_range(0x80..=0x10FFFF)?).unwrap_or('\u{80}'))
+    } else {
+        match u.int_in_range(0x21u8..=0x7E)? {
+            b'"' => Ok('!'),
+            c => Ok(c as char),
+        }
+    }
+}
+
+impl<'a, 't> Arbitrary<'a> for EntityTag<'t> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let weak = u.arbitrary()?;
+        let len = u.arbitrary_len::<u8>()?;
+
+        let mut tag = String::with_capacity(len);
+
+        for _ in 0..len {
+            tag.push(arbitrary_etagc(u)?);
+        }
+
+        Ok(EntityTag {
+            weak,
+            tag: Cow::from(tag),
+        })
+    }
+}
+
+impl<'a, 't> Arbitrary<'a> for EntityTagList<'t> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1u8, 8)? {
+            Ok(EntityTagList::Any)
+        } else {
+            // 1#entity-tag
+            let mut tags = vec![u.arbitrary()?];
+
+            for etag in u.arbitrary_iter()? {
+                tags.push(etag?);
+            }
+
+            Ok(EntityTagList::Tags(tags))
+        }
+    }
+}
+
+/// A field value which is either a valid entity tag or a near-valid one, which is a valid entity tag with a single mutation (such as a missing `DQUOTE`, an embedded control character, a lowercase `w/` or surrounding whitespace).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EntityTagInput(pub String);
+
+fn arbitrary_position(u: &mut Unstructured<'_>, s: &str) -> Result<usize> {
+    let i = u.choose_index(s.chars().count() + 1)?;
+
+    Ok(s.char_indices().nth(i).map(|(p, _)| p).unwrap_or(s.len()))
+}
+
+impl<'a> Arbitrary<'a> for EntityTagInput {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let etag: EntityTag = u.arbitrary()?;
+
+        let mut s = etag.to_string();
+
+        if u.arbitrary()? {
+            match u.choose_index(10)? {
+                0 => {
+                    s.remove(if etag.weak { 2 } else { 0 });
+                },
+                1 => {
+                    s.pop();
+                },
+                2 => {
+                    let p = arbitrary_position(u, &s)?;
+
+                    s.insert(p, '"');
+                },
+                3 => {
+                    let p = arbitrary_position(u, &s)?;
+                    let c = *u.choose(&['\0', '\t', '\n', '\r', ' ', '\x7F'])?;
+
+                    s.insert(p, c);
+                },
+                4 => {
+                    if etag.weak {
+                        s.replace_range(..1, "w");
+                    } else {
+                        s.insert_str(0, "w/");
+                    }
+                },
+                5 => {
+                    s.insert(0, ' ');
+                    s.push('\t');
+                },
+                6 => s.retain(|c| c != '"'),
+                7 => {
+                    let p = arbitrary_position(u, &s)?;
+
+                    s.truncate(p);
+                },
+                8 => {
+                    let other: EntityTag = u.arbitrary()?;
+
+                    s.push_str(", ");
+                    s.push_str(&other.to_string());
+                },
+                _ => s = String::from("*"),
+            }
+        }
+
+        Ok(EntityTagInput(s))
+    }
+}
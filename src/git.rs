@@ -0,0 +1,40 @@
+use alloc::borrow::Cow;
+
+use crate::{EntityTag, EntityTagError};
+
+impl<'t> EntityTag<'t> {
+    /// Construct a strong EntityTag from the hash of a Git blob object, which is 40 (SHA-1) or 64 (SHA-256) hexadecimal digits. The hash is normalized to lowercase.
+    pub fn from_git_blob_hash<S: ?Sized + AsRef<str>>(
+        hash: &S,
+    ) -> Result<EntityTag<'static>, EntityTagError> {
+        let hash = hash.as_ref();
+
+        if !(hash.len() == 40 || hash.len() == 64) || !hash.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(EntityTagError::InvalidTag);
+        }
+
+        Ok(EntityTag {
+            weak: false, tag: Cow::from(hash.to_ascii_lowercase())
+        })
+    }
+
+    /// Construct a strong EntityTag whose opaque tag is the SHA-1 hash that Git computes for a blob object of the data (`git hash-object`).
+    #[cfg(feature = "sha1")]
+    pub fn from_git_blob<S: ?Sized + AsRef<[u8]>>(data: &S) -> EntityTag<'static> {
+        use alloc::string::ToString;
+
+        use sha1::{Digest, Sha1};
+
+        let data = data.as_ref();
+
+        let mut hasher = Sha1::new();
+        hasher.update(b"blob ");
+        hasher.update(data.len().to_string().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(data);
+
+        EntityTag {
+            weak: false, tag: Cow::from(crate::encode_hex(&hasher.finalize()))
+        }
+    }
+}
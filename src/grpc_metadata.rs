@@ -0,0 +1,72 @@
+//! Helpers for gRPC metadata mirroring the header fields related to entity tags, based on the `tonic` crate.
+
+use alloc::string::ToString;
+use core::convert::TryFrom;
+
+use tonic::metadata::{Ascii, MetadataMap, MetadataValue};
+
+use crate::{EntityTag, EntityTagError, EntityTagList};
+
+/// `etag`
+pub const ETAG: &str = "etag";
+/// `if-match`
+pub const IF_MATCH: &str = "if-match";
+/// `if-none-match`
+pub const IF_NONE_MATCH: &str = "if-none-match";
+
+fn to_str(value: &MetadataValue<Ascii>) -> Result<&str, EntityTagError> {
+    // `obs-text` is not visible ASCII, so `MetadataValue::to_str` cannot be used
+    core::str::from_utf8(value.as_bytes()).map_err(|_| EntityTagError::InvalidTag)
+}
+
+fn get_list<'a>(
+    metadata: &'a MetadataMap,
+    key: &'static str,
+) -> Result<Option<EntityTagList<'a>>, EntityTagError> {
+    EntityTagList::join_values(metadata.get_all(key).iter().map(|value| to_str(value)))
+}
+
+/// Find the `etag` metadata and parse it. Returns `Ok(None)` if the key is absent.
+#[inline]
+pub fn get_etag(metadata: &MetadataMap) -> Result<Option<EntityTag<'_>>, EntityTagError> {
+    metadata.get(ETAG).map(EntityTag::try_from).transpose()
+}
+
+/// Set the `etag` metadata.
+#[inline]
+pub fn insert_etag(metadata: &mut MetadataMap, etag: &EntityTag) {
+    metadata.insert(ETAG, MetadataValue::from(etag));
+}
+
+/// Find the `if-match` metadata and parse them into an `EntityTagList`. Multiple occurrences are joined together. Returns `Ok(None)` if the key is absent.
+#[inline]
+pub fn get_if_match(metadata: &MetadataMap) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    get_list(metadata, IF_MATCH)
+}
+
+/// Find the `if-none-match` metadata and parse them into an `EntityTagList`. Multiple occurrences are joined together. Returns `Ok(None)` if the key is absent.
+#[inline]
+pub fn get_if_none_match(
+    metadata: &MetadataMap,
+) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    get_list(metadata, IF_NONE_MATCH)
+}
+
+impl<'t> TryFrom<&'t MetadataValue<Ascii>> for EntityTag<'t> {
+    type Error = EntityTagError;
+
+    /// Parse a metadata value. The opaque tag is borrowed from the metadata value.
+    #[inline]
+    fn try_from(value: &'t MetadataValue<Ascii>) -> Result<Self, Self::Error> {
+        EntityTag::from_str(to_str(value)?)
+    }
+}
+
+impl<'a, 't> From<&'a EntityTag<'t>> for MetadataValue<Ascii> {
+    /// Format the entity tag as a metadata value.
+    #[inline]
+    fn from(etag: &'a EntityTag<'t>) -> Self {
+        // a constructed entity tag only contains `etagc` characters, which are always valid in a metadata value
+        MetadataValue::try_from(etag.to_string()).unwrap()
+    }
+}
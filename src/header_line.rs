@@ -0,0 +1,40 @@
+use alloc::{format, string::String};
+
+use crate::{EntityTag, EntityTagError, HeaderLineError};
+
+impl<'t> EntityTag<'t> {
+    /// Parse a raw HTTP/1.1 header line of the `ETag` header field, such as `ETag: W/"x"\r\n`. The field name is case-insensitive, and the optional whitespace around the field value and the line terminator are removed.
+    pub fn from_header_line<S: ?Sized + AsRef<[u8]>>(line: &'t S) -> Result<Self, HeaderLineError> {
+        let line = line.as_ref();
+
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        let name_length = Self::HEADER_NAME.len();
+
+        match line.get(..=name_length) {
+            Some(prefix)
+                if prefix[..name_length].eq_ignore_ascii_case(Self::HEADER_NAME.as_bytes())
+                    && prefix[name_length] == b':' => {},
+            _ => return Err(HeaderLineError::InvalidFieldName),
+        }
+
+        let value = core::str::from_utf8(&line[(name_length + 1)..])
+            .map_err(|_| EntityTagError::InvalidTag)?
+            .trim_matches(|c| c == ' ' || c == '\t');
+
+        Ok(Self::from_str(value)?)
+    }
+
+    /// Format this entity tag as an HTTP/1.1 header line of the `ETag` header field without the line terminator, such as `ETag: W/"x"`.
+    #[inline]
+    pub fn to_header_string(&self) -> String {
+        format!("{}: {}", Self::HEADER_NAME, self)
+    }
+
+    /// Format this entity tag as an HTTP/1.1 header line of the `ETag` header field terminated by CRLF, such as `ETag: W/"x"\r\n`, which can be written to a CGI or SCGI response directly.
+    #[inline]
+    pub fn to_header_line(&self) -> String {
+        format!("{}: {}\r\n", Self::HEADER_NAME, self)
+    }
+}
@@ -0,0 +1,34 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::EntityTagError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Possible errors of parsing an `ETag` header line.
+pub enum HeaderLineError {
+    /// The line does not start with the `ETag` field name followed by a colon.
+    InvalidFieldName,
+    /// The field value is not a valid entity tag.
+    InvalidEntityTag(EntityTagError),
+}
+
+impl From<EntityTagError> for HeaderLineError {
+    #[inline]
+    fn from(error: EntityTagError) -> Self {
+        HeaderLineError::InvalidEntityTag(error)
+    }
+}
+
+impl Display for HeaderLineError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            HeaderLineError::InvalidFieldName => f.write_str("the field name is not ETag"),
+            HeaderLineError::InvalidEntityTag(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for HeaderLineError {}
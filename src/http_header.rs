@@ -0,0 +1,290 @@
+//! Helpers for the header fields related to entity tags, based on the `http` crate.
+
+use alloc::{string::ToString, vec};
+use core::convert::TryFrom;
+
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method};
+
+use crate::{
+    buffer, EntityTag, EntityTagBytes, EntityTagError, EntityTagList, PreconditionError,
+    PreconditionFailure, PreconditionHeader, PreconditionStep, PreconditionTrace,
+};
+
+/// `etag`
+#[deprecated(note = "use `EntityTag::HEADER` instead")]
+pub const ETAG: HeaderName = EntityTag::HEADER;
+/// `if-match`
+#[deprecated(note = "use `EntityTag::IF_MATCH_HEADER` instead")]
+pub const IF_MATCH: HeaderName = EntityTag::IF_MATCH_HEADER;
+/// `if-none-match`
+#[deprecated(note = "use `EntityTag::IF_NONE_MATCH_HEADER` instead")]
+pub const IF_NONE_MATCH: HeaderName = EntityTag::IF_NONE_MATCH_HEADER;
+/// `if-range`
+#[deprecated(note = "use `EntityTag::IF_RANGE_HEADER` instead")]
+pub const IF_RANGE: HeaderName = EntityTag::IF_RANGE_HEADER;
+
+fn get_list(
+    headers: &HeaderMap,
+    name: HeaderName,
+) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    EntityTagList::join_values(headers
+            .get_all(name)
+            .iter()
+            // `obs-text` is not visible ASCII, so `HeaderValue::to_str` cannot be used
+            .map(|value| {
+                core::str::from_utf8(value.as_bytes()).map_err(|_| EntityTagError::InvalidTag)
+            }))
+}
+
+/// Find the `If-Match` header fields and parse them into an `EntityTagList`. Multiple occurrences are joined together. Returns `Ok(None)` if the header field is absent.
+#[inline]
+pub fn get_if_match(headers: &HeaderMap) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    get_list(headers, EntityTag::IF_MATCH_HEADER)
+}
+
+/// Find the `If-None-Match` header fields and parse them into an `EntityTagList`. Multiple occurrences are joined together. Returns `Ok(None)` if the header field is absent.
+#[inline]
+pub fn get_if_none_match(headers: &HeaderMap) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    get_list(headers, EntityTag::IF_NONE_MATCH_HEADER)
+}
+
+/// The header fields which a `304 Not Modified` response needs to carry if they would have been sent in a `200 OK` response, except for `ETag`.
+const NOT_MODIFIED_HEADERS: [HeaderName; 5] =
+    [header::CACHE_CONTROL, header::CONTENT_LOCATION, header::DATE, header::EXPIRES, header::VARY];
+
+/// Build the header fields of a `304 Not Modified` response, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-15.4.5), from the header fields which would have been sent in a `200 OK` response.
+///
+/// `Cache-Control`, `Content-Location`, `Date`, `ETag`, `Expires` and `Vary` are copied, and other representation metadata is dropped. If `etag` is given, it is used as the `ETag` header field. `Last-Modified` is only kept when there is no `ETag`.
+pub fn not_modified_headers(etag: Option<&EntityTag>, headers: &HeaderMap) -> HeaderMap {
+    let mut not_modified = HeaderMap::new();
+
+    for name in NOT_MODIFIED_HEADERS.iter() {
+        for value in headers.get_all(name) {
+            not_modified.append(name.clone(), value.clone());
+        }
+    }
+
+    match etag {
+        Some(etag) => {
+            not_modified.insert(EntityTag::HEADER, HeaderValue::from(etag));
+        },
+        None => {
+            if let Some(value) = headers.get(EntityTag::HEADER) {
+                not_modified.insert(EntityTag::HEADER, value.clone());
+            }
+        },
+    }
+
+    if !not_modified.contains_key(EntityTag::HEADER) {
+        if let Some(value) = headers.get(header::LAST_MODIFIED) {
+            not_modified.insert(header::LAST_MODIFIED, value.clone());
+        }
+    }
+
+    not_modified
+}
+
+/// Evaluate the `If-Match` and `If-None-Match` preconditions of a request in the order defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2). `current` is the entity tag of the selected representation, or `None` if the target resource has no current representation.
+///
+/// A malformed `If-Match` header field never matches, and a malformed `If-None-Match` header field is ignored. A failed `If-None-Match` of a `GET` or `HEAD` request results in an error whose `is_not_modified` returns `true`.
+#[inline]
+pub fn evaluate_preconditions(
+    method: &Method,
+    headers: &HeaderMap,
+    current: Option<&EntityTag>,
+) -> Result<(), PreconditionError> {
+    evaluate(method, headers, current, None)
+}
+
+/// Evaluate the preconditions of a request like `evaluate_preconditions` does, and explain how they were evaluated, e.g. to tell API clients why they got `412 Precondition Failed`.
+#[inline]
+pub fn evaluate_preconditions_traced(
+    method: &Method,
+    headers: &HeaderMap,
+    current: Option<&EntityTag>,
+) -> (Result<(), PreconditionError>, PreconditionTrace) {
+    let mut trace = PreconditionTrace::default();
+
+    let outcome = evaluate(method, headers, current, Some(&mut trace));
+
+    (outcome, trace)
+}
+
+fn evaluate(
+    method: &Method,
+    headers: &HeaderMap,
+    current: Option<&EntityTag>,
+    trace: Option<&mut PreconditionTrace>,
+) -> Result<(), PreconditionError> {
+    let outcome = evaluate_headers(method, headers, current, trace);
+
+    if let Err(error) = &outcome {
+        if error.is_not_modified() {
+            count!("entity_tag_not_modified_total", 1);
+        } else {
+            count!("entity_tag_precondition_failed_total", 1);
+        }
+    }
+
+    outcome
+}
+
+fn evaluate_headers(
+    method: &Method,
+    headers: &HeaderMap,
+    current: Option<&EntityTag>,
+    mut trace: Option<&mut PreconditionTrace>,
+) -> Result<(), PreconditionError> {
+    match get_if_match(headers) {
+        Ok(Some(if_match)) => match trace.as_deref_mut() {
+            Some(trace) => trace.record(if_match.trace_if_match(current))?,
+            None => if_match.check_if_match(current)?,
+        },
+        Ok(None) => (),
+        Err(_error) => {
+            debug!(error = %_error, "If-Match: the field value is malformed");
+
+            let error = PreconditionError::new(
+                PreconditionHeader::IfMatch,
+                PreconditionFailure::NoneMatched,
+            );
+
+            let step = PreconditionStep::malformed(PreconditionHeader::IfMatch, Err(error));
+
+            return match trace {
+                Some(trace) => trace.record(step),
+                None => Err(error),
+            };
+        },
+    }
+
+    match get_if_none_match(headers) {
+        Ok(Some(if_none_match)) => {
+            let outcome = match trace.as_deref_mut() {
+                Some(trace) => trace.record(if_none_match.trace_if_none_match(current)),
+                None => if_none_match.check_if_none_match(current),
+            };
+
+            if let Err(error) = outcome {
+                return if method == Method::GET || method == Method::HEAD {
+                    debug!(%method, "If-None-Match: responding with 304 Not Modified");
+
+                    if let Some(trace) = trace {
+                        trace.set_not_modified();
+                    }
+
+                    Err(error.into_not_modified())
+                } else {
+                    Err(error)
+                };
+            }
+        },
+        Ok(None) => (),
+        Err(_error) => {
+            debug!(error = %_error, "If-None-Match: the malformed field value is ignored");
+
+            if let Some(trace) = trace {
+                trace
+                    .record(PreconditionStep::malformed(PreconditionHeader::IfNoneMatch, Ok(())))?;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+impl<'t> EntityTag<'t> {
+    /// `etag`, which can be inserted into a `HeaderMap` without parsing the name.
+    pub const HEADER: HeaderName = header::ETAG;
+    /// `if-match`, which can be looked up in a `HeaderMap` without parsing the name.
+    pub const IF_MATCH_HEADER: HeaderName = header::IF_MATCH;
+    /// `if-none-match`, which can be looked up in a `HeaderMap` without parsing the name.
+    pub const IF_NONE_MATCH_HEADER: HeaderName = header::IF_NONE_MATCH;
+    /// `if-range`, which can be looked up in a `HeaderMap` without parsing the name.
+    pub const IF_RANGE_HEADER: HeaderName = header::IF_RANGE;
+}
+
+impl<'t> TryFrom<&'t HeaderValue> for EntityTag<'t> {
+    type Error = EntityTagError;
+
+    /// Parse the value of an `ETag` header field. The opaque tag is borrowed from the header value.
+    #[inline]
+    fn try_from(value: &'t HeaderValue) -> Result<Self, Self::Error> {
+        // `obs-text` is not visible ASCII, so `HeaderValue::to_str` cannot be used
+        let s = core::str::from_utf8(value.as_bytes()).map_err(|_| EntityTagError::InvalidTag)?;
+
+        EntityTag::from_str(s)
+    }
+}
+
+impl TryFrom<HeaderValue> for EntityTag<'static> {
+    type Error = EntityTagError;
+
+    /// Parse the value of an `ETag` header field.
+    #[inline]
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        EntityTag::try_from(&value).map(EntityTag::into_owned)
+    }
+}
+
+/// Convert a formatted entity tag or entity tag list into a header value. It fails if the value contains octets which are not allowed in a header field, such as CR and LF, which only an entity tag made by an unchecked constructor can have.
+#[inline]
+pub(crate) fn to_header_value(bytes: &[u8]) -> Result<HeaderValue, EntityTagError> {
+    HeaderValue::from_bytes(bytes).map_err(|_| EntityTagError::InvalidTag)
+}
+
+impl<'a, 't> From<&'a EntityTag<'t>> for HeaderValue {
+    /// Format the entity tag as the value of an `ETag` header field. An entity tag made by an unchecked constructor with octets which are not allowed in a header field results in an empty value rather than a malformed header field.
+    fn from(etag: &'a EntityTag<'t>) -> Self {
+        let tag = etag.get_tag_cow();
+
+        let mut bytes = vec![0; buffer::formatted_len(etag.weak, tag)];
+
+        // the buffer has the exact length
+        buffer::write_to_slice(etag.weak, tag, &mut bytes).unwrap();
+
+        to_header_value(&bytes).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+}
+
+impl<'t> From<EntityTag<'t>> for HeaderValue {
+    /// Format the entity tag as the value of an `ETag` header field.
+    #[inline]
+    fn from(etag: EntityTag<'t>) -> Self {
+        HeaderValue::from(&etag)
+    }
+}
+
+impl<'a, 't> TryFrom<&'a EntityTagList<'t>> for HeaderValue {
+    type Error = EntityTagError;
+
+    /// Format the list as the value of an `If-Match` or `If-None-Match` header field. It fails if the list is empty, which is not a valid value of the fields, or if an entity tag made by an unchecked constructor has octets which are not allowed in a header field.
+    #[inline]
+    fn try_from(list: &'a EntityTagList<'t>) -> Result<Self, Self::Error> {
+        if let EntityTagList::Tags(tags) = list {
+            if tags.is_empty() {
+                return Err(EntityTagError::InvalidTag);
+            }
+        }
+
+        to_header_value(list.to_string().as_bytes())
+    }
+}
+
+impl<'t> TryFrom<&'t HeaderValue> for EntityTagBytes<'t> {
+    type Error = EntityTagError;
+
+    /// Parse the value of an `ETag` header field, keeping the `obs-text` octets which are not valid UTF-8. The opaque tag is borrowed from the header value.
+    #[inline]
+    fn try_from(value: &'t HeaderValue) -> Result<Self, Self::Error> {
+        EntityTagBytes::from_bytes(value.as_bytes())
+    }
+}
+
+impl<'a, 't> From<&'a EntityTagBytes<'t>> for HeaderValue {
+    /// Format the entity tag as the value of an `ETag` header field without any escaping. An entity tag made by `new_unchecked` with octets which are not allowed in a header field results in an empty value.
+    #[inline]
+    fn from(etag: &'a EntityTagBytes<'t>) -> Self {
+        to_header_value(&etag.to_header_bytes()).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+}
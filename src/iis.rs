@@ -0,0 +1,29 @@
+use alloc::borrow::Cow;
+
+use crate::{EntityTag, EntityTagError, EntityTagRef, ParseOptions};
+
+impl<'t> EntityTag<'t> {
+    /// Parse an entity tag emitted by IIS, such as `"86c41a6a7a9be1:0"`, tolerating the ways it is commonly mangled by proxies (missing double quotes, surrounding whitespace, an added weakness indicator in either case).
+    ///
+    /// An opaque tag in the IIS format consists of the last modified time and a change number, separated by a colon. The change number may differ between the servers of a farm for the same file, so it is removed in order that such entity tags compare equal. Other opaque tags are kept as they are.
+    pub fn from_str_iis<S: ?Sized + AsRef<str>>(etag: &'t S) -> Result<Self, EntityTagError> {
+        let etag = EntityTagRef::from_str_with(etag, ParseOptions::lenient())?;
+        let tag = etag.get_tag();
+
+        let tag = match tag.rsplit_once(':') {
+            Some((modified, change))
+                if !modified.is_empty()
+                    && !change.is_empty()
+                    && modified.bytes().all(|c| c.is_ascii_hexdigit())
+                    && change.bytes().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                modified
+            },
+            _ => tag,
+        };
+
+        Ok(EntityTag {
+            weak: etag.weak, tag: Cow::from(tag)
+        })
+    }
+}
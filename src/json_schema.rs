@@ -0,0 +1,31 @@
+use alloc::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{EntityTag, ENTITY_TAG_PATTERN};
+
+impl<'t> JsonSchema for EntityTag<'t> {
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("EntityTag")
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("entity_tag::EntityTag")
+    }
+
+    #[inline]
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": ENTITY_TAG_PATTERN,
+            "description": "An entity tag, such as `\"xyzzy\"` or `W/\"xyzzy\"`.",
+        })
+    }
+}
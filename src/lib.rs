@@ -6,6 +6,8 @@ This crate provides a `EntityTag` structure and functions to deal with the ETag
 ## Examples
 
 ```rust
+# #[cfg(feature = "alloc")]
+# {
 use entity_tag::EntityTag;
 
 let etag1 = EntityTag::with_str(true, "foo").unwrap();
@@ -17,14 +19,18 @@ assert_eq!(false, etag2.weak);
 assert!(etag1.weak_eq(&etag2));
 assert!(etag1.strong_ne(&etag2));
 
+# #[cfg(feature = "generate")]
+# {
 let etag3 = EntityTag::from_data(&[102, 111, 111]);
 assert_eq!("\"972Sf7Z4eu8\"", etag3.to_string());
+# }
 
-# #[cfg(feature = "std")]
+# #[cfg(all(feature = "std", feature = "generate"))]
 # {
 let etag4 = EntityTag::from_file_meta(&std::fs::File::open("tests/data/P1060382.JPG").unwrap().metadata().unwrap());
 println!("{}", etag4) // W/"HRScBWR0Mf4"
 # }
+# }
 ```
 
 ## No Std
@@ -36,28 +42,305 @@ Disable the default features to compile this crate without std.
 version = "*"
 default-features = false
 ```
+
+Without the `alloc` feature, only the `EntityTagRef` type, which borrows its opaque tag, is available for parsing, comparing and formatting entity tags. Enable the `alloc` feature to use the owned `EntityTag` type and the rest of this crate without std.
+
+```toml
+[dependencies.entity-tag]
+version = "*"
+default-features = false
+features = ["alloc"]
+```
+
+The functions generating entity tags from data or files, such as `EntityTag::from_data` and `EntityTagHasher`, need the `generate` feature, which is enabled by default and pulls in the `base64` and `highway` crates. Consumers which only parse and compare entity tags, such as proxies and clients, can leave it out for a leaner dependency tree.
+
+```toml
+[dependencies.entity-tag]
+version = "*"
+default-features = false
+features = ["alloc", "generate"]
+```
 */
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
-mod entity_tag_error;
+/// Emit a debug-level `tracing` event if the `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        tracing::debug!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+/// Increase a `metrics` counter by the given value if the `metrics` feature is enabled.
+#[cfg(feature = "metrics")]
+#[allow(unused_macros)]
+macro_rules! count {
+    ($name:literal, $value:expr $(, $label:literal => $label_value:literal)*) => {
+        metrics::counter!($name $(, $label => $label_value)*).increment($value as u64)
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+#[allow(unused_macros)]
+macro_rules! count {
+    ($($arg:tt)*) => {};
+}
 
-use alloc::{borrow::Cow, string::String};
-use core::{
-    fmt::{self, Display, Formatter, Write},
-    hash::Hasher,
-};
 #[cfg(feature = "std")]
-use std::fs::Metadata;
+mod actix_files;
+#[cfg(feature = "alloc")]
+mod binary;
+#[cfg(feature = "alloc")]
+mod binary_decode_error;
+mod buffer;
+mod buffer_too_small;
+#[cfg(feature = "alloc")]
+mod cache_policy;
+#[cfg(feature = "alloc")]
+mod canonicalize;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "client-cache")]
+pub mod client_cache;
+#[cfg(feature = "alloc")]
+mod components;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+#[cfg(feature = "generate")]
+pub mod digest;
+#[cfg(all(feature = "generate", feature = "embedded-io"))]
+mod embedded_io_reader;
+#[cfg(feature = "alloc")]
+mod entity_tag_bytes;
+mod entity_tag_error;
+#[cfg(feature = "generate")]
+mod entity_tag_hasher;
+#[cfg(feature = "alloc")]
+mod entity_tag_interner;
+#[cfg(feature = "alloc")]
+mod entity_tag_list;
+#[cfg(feature = "alloc")]
+mod entity_tag_list_iter;
+#[cfg(feature = "alloc")]
+mod entity_tag_map;
+#[cfg(all(feature = "std", feature = "generate"))]
+mod entity_tag_reader;
+mod entity_tag_ref;
+#[cfg(feature = "alloc")]
+mod entity_tag_store;
+#[cfg(all(
+    feature = "generate",
+    any(feature = "futures-core", all(feature = "http-body", feature = "bytes"))
+))]
+mod entity_tag_stream;
+#[cfg(all(feature = "std", feature = "generate"))]
+mod entity_tag_writer;
+#[cfg(feature = "generate")]
+mod epoch;
+#[cfg(all(feature = "std", feature = "generate"))]
+mod file_tag_cache;
+#[cfg(all(feature = "std", feature = "generate", feature = "xattr"))]
+mod file_xattr;
+#[cfg(all(feature = "alloc", feature = "arbitrary"))]
+pub mod fuzz;
+#[cfg(feature = "alloc")]
+mod git;
+#[cfg(all(feature = "alloc", feature = "tonic"))]
+pub mod grpc_metadata;
+#[cfg(feature = "alloc")]
+mod header_line;
+#[cfg(feature = "alloc")]
+mod header_line_error;
 #[cfg(feature = "std")]
-use std::time::UNIX_EPOCH;
-
-use base64::Engine;
+mod http_date;
+#[cfg(all(feature = "alloc", feature = "http"))]
+pub mod http_header;
+#[cfg(feature = "alloc")]
+mod iis;
+#[cfg(all(feature = "alloc", feature = "schemars"))]
+mod json_schema;
+#[cfg(feature = "alloc")]
+mod map_tag;
+#[cfg(all(feature = "alloc", feature = "utoipa"))]
+pub mod openapi;
+#[cfg(feature = "generate")]
+mod page;
+mod parse_options;
+#[cfg(feature = "alloc")]
+mod precondition;
+#[cfg(feature = "alloc")]
+mod precondition_error;
+#[cfg(feature = "alloc")]
+mod precondition_trace;
+#[cfg(feature = "alloc")]
+mod prefix;
+#[cfg(all(feature = "alloc", any(feature = "sha1", feature = "md-5", feature = "sha2")))]
+mod rails;
+#[cfg(all(feature = "alloc", feature = "redis"))]
+mod redis_storage;
+#[cfg(feature = "alloc")]
+mod resource_version;
+#[cfg(feature = "alloc")]
+mod revalidation;
+#[cfg(all(feature = "alloc", feature = "rocksdb"))]
+mod rocksdb_storage;
+#[cfg(feature = "alloc")]
+mod row_version_tag;
+#[cfg(feature = "generate")]
+mod sampled;
+#[cfg(all(feature = "alloc", feature = "serde"))]
+pub mod serde_forms;
+#[cfg(all(feature = "alloc", feature = "serde"))]
+mod serde_impl;
+#[cfg(feature = "alloc")]
+mod shared_entity_tag;
+#[cfg(all(feature = "std", feature = "generate"))]
+mod sidecar;
+#[cfg(all(feature = "alloc", feature = "sled"))]
+mod sled_storage;
+#[cfg(feature = "sqlx")]
+mod sql;
+#[cfg(all(feature = "alloc", feature = "rusqlite"))]
+mod sqlite;
+#[cfg(all(feature = "alloc", feature = "proptest"))]
+pub mod strategy;
+#[cfg(feature = "alloc")]
+mod strong_compared;
+#[cfg(feature = "alloc")]
+mod structured_field;
+#[cfg(feature = "alloc")]
+mod structured_field_error;
+#[cfg(feature = "generate")]
+mod tag_encoding;
+#[cfg(feature = "alloc")]
+mod tag_storage;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(all(feature = "alloc", feature = "tide"))]
+pub mod tide_middleware;
+#[cfg(all(feature = "alloc", feature = "headers"))]
+mod typed_header;
+#[cfg(feature = "ufmt")]
+mod ufmt_write;
+#[cfg(feature = "alloc")]
+mod validated_tag;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+pub mod webdav;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+#[cfg(feature = "alloc")]
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "generate")]
+use core::hash::Hasher;
+#[cfg(all(feature = "std", feature = "generate"))]
+use std::fs::Metadata;
+#[cfg(all(feature = "std", feature = "generate"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "alloc")]
+pub use binary_decode_error::BinaryDecodeError;
+pub use buffer_too_small::BufferTooSmall;
+#[cfg(feature = "alloc")]
+pub use cache_policy::CachePolicy;
+#[cfg(feature = "alloc")]
+pub use entity_tag_bytes::EntityTagBytes;
 pub use entity_tag_error::EntityTagError;
+#[cfg(feature = "generate")]
+pub use entity_tag_hasher::EntityTagHasher;
+#[cfg(feature = "alloc")]
+pub use entity_tag_interner::EntityTagInterner;
+#[cfg(feature = "alloc")]
+pub use entity_tag_list::EntityTagList;
+#[cfg(feature = "alloc")]
+pub use entity_tag_list_iter::{EntityTagListItem, EntityTagListIter};
+#[cfg(feature = "alloc")]
+pub use entity_tag_map::EntityTagMap;
+#[cfg(all(feature = "std", feature = "generate"))]
+pub use entity_tag_reader::EntityTagReader;
+pub use entity_tag_ref::EntityTagRef;
+#[cfg(feature = "alloc")]
+pub use entity_tag_store::EntityTagStore;
+#[cfg(all(
+    feature = "generate",
+    any(feature = "futures-core", all(feature = "http-body", feature = "bytes"))
+))]
+pub use entity_tag_stream::EntityTagStream;
+#[cfg(all(feature = "std", feature = "generate"))]
+pub use entity_tag_writer::EntityTagWriter;
+#[cfg(feature = "generate")]
+use epoch::salt;
+#[cfg(feature = "generate")]
+pub use epoch::{bump_epoch, epoch, set_epoch};
+#[cfg(all(feature = "std", feature = "generate"))]
+pub use file_tag_cache::FileTagCache;
+#[cfg(feature = "alloc")]
+pub use header_line_error::HeaderLineError;
+#[cfg(feature = "generate")]
 use highway::HighwayHasher;
+pub use parse_options::ParseOptions;
+#[cfg(feature = "alloc")]
+pub use precondition::{evaluate_batch, BatchOutcome, Precondition};
+#[cfg(feature = "alloc")]
+pub use precondition_error::{PreconditionError, PreconditionFailure, PreconditionHeader};
+#[cfg(feature = "alloc")]
+pub use precondition_trace::{Comparison, PreconditionStep, PreconditionTrace};
+#[cfg(all(feature = "alloc", any(feature = "sha1", feature = "md-5", feature = "sha2")))]
+pub use rails::RailsDigest;
+#[cfg(all(feature = "alloc", feature = "redis"))]
+pub use redis_storage::RedisTagStorage;
+#[cfg(feature = "alloc")]
+pub use resource_version::ResourceVersion;
+#[cfg(feature = "alloc")]
+pub use revalidation::Revalidation;
+#[cfg(feature = "alloc")]
+pub use row_version_tag::RowVersionTag;
+#[cfg(feature = "alloc")]
+pub use shared_entity_tag::SharedEntityTag;
+#[cfg(feature = "alloc")]
+pub use strong_compared::StrongCompared;
+#[cfg(feature = "alloc")]
+pub use structured_field_error::StructuredFieldError;
+#[cfg(feature = "generate")]
+pub use tag_encoding::TagEncoding;
+#[cfg(feature = "alloc")]
+pub use tag_storage::TagStorage;
+#[cfg(feature = "alloc")]
+pub use validated_tag::ValidatedTag;
+
+/// The pattern of the `entity-tag` rule, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3), for schemas.
+#[cfg(all(feature = "alloc", any(feature = "schemars", feature = "utoipa")))]
+const ENTITY_TAG_PATTERN: &str = r#"^(W/)?"[^"\u0000- \u007F]*"$"#;
+
+/// Encode bytes into lowercase hexadecimal digits.
+#[cfg(all(feature = "alloc", any(feature = "sha1", feature = "md-5", feature = "sha2")))]
+fn encode_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut s = String::with_capacity(bytes.len() * 2);
+
+    for b in bytes {
+        s.push(HEX[(b >> 4) as usize] as char);
+        s.push(HEX[(b & 0xF) as usize] as char);
+    }
+
+    s
+}
 
+#[cfg(feature = "alloc")]
 /// An entity tag, defined in [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3).
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EntityTag<'t> {
@@ -67,11 +350,19 @@ pub struct EntityTag<'t> {
     tag:      Cow<'t, str>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     /// `ETag`
     pub const HEADER_NAME: &'static str = "ETag";
+    /// `If-Match`
+    pub const IF_MATCH_HEADER_NAME: &'static str = "If-Match";
+    /// `If-None-Match`
+    pub const IF_NONE_MATCH_HEADER_NAME: &'static str = "If-None-Match";
+    /// `If-Range`
+    pub const IF_RANGE_HEADER_NAME: &'static str = "If-Range";
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     /// Construct a new EntityTag without checking.
     #[allow(clippy::missing_safety_doc)]
@@ -90,6 +381,7 @@ impl<'t> EntityTag<'t> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     /// Construct a new EntityTag without checking.
     #[allow(clippy::missing_safety_doc)]
@@ -112,35 +404,16 @@ impl<'t> EntityTag<'t> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     #[inline]
     fn check_unquoted_tag(s: &str) -> Result<(), EntityTagError> {
-        if s.bytes().all(|c| c == b'\x21' || (b'\x23'..=b'\x7e').contains(&c) || c >= b'\x80') {
-            Ok(())
-        } else {
-            Err(EntityTagError::InvalidTag)
-        }
+        EntityTagRef::check_unquoted_tag(s)
     }
 
-    fn check_tag(s: &str) -> Result<bool, EntityTagError> {
-        let (s, quoted) =
-            if let Some(stripped) = s.strip_prefix('"') { (stripped, true) } else { (s, false) };
-
-        let s = if quoted {
-            if let Some(stripped) = s.strip_suffix('"') {
-                stripped
-            } else {
-                return Err(EntityTagError::MissingClosingDoubleQuote);
-            }
-        } else {
-            s
-        };
-
-        // now check the ETag characters
-
-        Self::check_unquoted_tag(s)?;
-
-        Ok(quoted)
+    #[inline]
+    fn unquote_tag(s: &str) -> Result<&str, EntityTagError> {
+        EntityTagRef::unquote_tag(s)
     }
 
     /// Construct a new EntityTag.
@@ -149,13 +422,22 @@ impl<'t> EntityTag<'t> {
         weak: bool,
         tag: S,
     ) -> Result<EntityTag<'static>, EntityTagError> {
-        let quoted = Self::check_tag(tag.as_ref())?;
+        let (start, end) = {
+            let s = tag.as_ref();
+
+            let unquoted = Self::unquote_tag(s)?;
+
+            // the opaque tag is a part of `s`
+            let start = unquoted.as_ptr() as usize - s.as_ptr() as usize;
+
+            (start, start + unquoted.len())
+        };
 
         let mut tag = tag.into();
 
-        if quoted {
-            tag.remove(tag.len() - 1);
-            tag.remove(0);
+        if start > 0 {
+            tag.truncate(end);
+            tag.drain(..start);
         }
 
         Ok(EntityTag {
@@ -170,63 +452,87 @@ impl<'t> EntityTag<'t> {
         weak: bool,
         tag: &'t S,
     ) -> Result<Self, EntityTagError> {
+        Ok(EntityTag {
+            weak,
+            tag: Cow::from(Self::unquote_tag(tag.as_ref())?),
+        })
+    }
+
+    /// Construct a new EntityTag from an arbitrary string, such as a title or a key controlled by users, without failing. Double quotes are not stripped.
+    ///
+    /// If the string contains any character which is not allowed in an opaque tag, such as a space, a double quote or a control character, those characters and `%` are percent-encoded, so that different strings always result in different opaque tags. Otherwise, the string is borrowed as it is.
+    pub fn with_str_lossy<S: ?Sized + AsRef<str>>(weak: bool, tag: &'t S) -> Self {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
         let tag = tag.as_ref();
 
-        let quoted = Self::check_tag(tag)?;
+        if Self::check_unquoted_tag(tag).is_ok() && !tag.contains('%') {
+            return EntityTag {
+                weak,
+                tag: Cow::from(tag),
+            };
+        }
+
+        let mut encoded = String::with_capacity(tag.len() + 8);
 
-        let tag = if quoted { &tag[1..(tag.len() - 1)] } else { tag };
+        for c in tag.chars() {
+            match c {
+                '%' | '\x00'..=' ' | '"' | '\x7F' => {
+                    let b = c as u8;
 
-        Ok(EntityTag {
+                    encoded.push('%');
+                    encoded.push(HEX[(b >> 4) as usize] as char);
+                    encoded.push(HEX[(b & 0xF) as usize] as char);
+                },
+                _ => encoded.push(c),
+            }
+        }
+
+        EntityTag {
             weak,
-            tag: Cow::from(tag),
-        })
+            tag: Cow::from(encoded),
+        }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
+    /// Parse an entity-tag at the beginning of `s`, returning it together with the remaining part of `s`.
     #[inline]
-    fn check_opaque_tag(s: &str) -> Result<(), EntityTagError> {
-        if let Some(s) = s.strip_prefix('"') {
-            if let Some(s) = s.strip_suffix('"') {
-                // now check the ETag characters
-                Self::check_unquoted_tag(s)
-            } else {
-                Err(EntityTagError::MissingClosingDoubleQuote)
-            }
-        } else {
-            Err(EntityTagError::MissingStartingDoubleQuote)
-        }
+    pub(crate) fn parse_prefix(s: &'t str) -> Result<(Self, &'t str), EntityTagError> {
+        let (etag, rest) = EntityTagRef::parse_prefix(s)?;
+
+        Ok((etag.into(), rest))
     }
 
     /// Parse and construct a new EntityTag from a `String`.
+    #[inline]
     pub fn from_string<S: AsRef<str> + Into<String>>(
         etag: S,
     ) -> Result<EntityTag<'static>, EntityTagError> {
-        let weak = {
+        Self::from_string_with(etag, ParseOptions::new())
+    }
+
+    /// Parse and construct a new EntityTag from a `String` with relaxations of the grammar.
+    pub fn from_string_with<S: AsRef<str> + Into<String>>(
+        etag: S,
+        options: ParseOptions,
+    ) -> Result<EntityTag<'static>, EntityTagError> {
+        let (weak, start, end) = {
             let s = etag.as_ref();
 
-            let (weak, opaque_tag) = if let Some(opaque_tag) = s.strip_prefix("W/") {
-                (true, opaque_tag)
-            } else {
-                (false, s)
-            };
+            let parsed = EntityTagRef::from_str_with(s, options)?;
 
-            Self::check_opaque_tag(opaque_tag)?;
+            // the opaque tag is a part of `s`
+            let start = parsed.tag.as_ptr() as usize - s.as_ptr() as usize;
 
-            weak
+            (parsed.weak, start, start + parsed.tag.len())
         };
 
         let mut tag = etag.into();
 
-        tag.remove(tag.len() - 1);
-
-        if weak {
-            unsafe {
-                tag.as_mut_vec().drain(0..3);
-            }
-        } else {
-            tag.remove(0);
-        }
+        tag.truncate(end);
+        tag.drain(..start);
 
         Ok(EntityTag {
             weak,
@@ -236,45 +542,187 @@ impl<'t> EntityTag<'t> {
 
     /// Parse and construct a new EntityTag from a `str`.
     #[allow(clippy::should_implement_trait)]
+    #[inline]
     pub fn from_str<S: ?Sized + AsRef<str>>(etag: &'t S) -> Result<Self, EntityTagError> {
-        let s = etag.as_ref();
+        Self::from_str_with(etag, ParseOptions::new())
+    }
 
-        let (weak, opaque_tag) = if let Some(opaque_tag) = s.strip_prefix("W/") {
-            (true, opaque_tag)
-        } else {
-            (false, s)
-        };
+    /// Parse and construct a new EntityTag from a `str` with relaxations of the grammar.
+    #[inline]
+    pub fn from_str_with<S: ?Sized + AsRef<str>>(
+        etag: &'t S,
+        options: ParseOptions,
+    ) -> Result<Self, EntityTagError> {
+        Ok(EntityTagRef::from_str_with(etag, options)?.into())
+    }
 
-        Self::check_opaque_tag(opaque_tag)?;
+    /// Construct a strong EntityTag whose opaque tag is the MD5 hash of the data in lowercase hexadecimal digits, which is the format generated by Django's `ConditionalGetMiddleware` and many legacy frameworks.
+    #[cfg(feature = "md-5")]
+    #[inline]
+    pub fn from_data_md5<S: ?Sized + AsRef<[u8]>>(data: &S) -> EntityTag<'static> {
+        use md5::{Digest, Md5};
 
-        Ok(EntityTag {
+        let tag = encode_hex(&Md5::digest(data.as_ref()));
+
+        EntityTag {
+            weak: false, tag: Cow::from(tag)
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+impl<'t> EntityTag<'t> {
+    /// Construct an EntityTag from a hash value, such as the one returned by `as_hash`. The opaque tag is the same as the one generated by `from_data` or `from_file_meta` for that hash value.
+    #[inline]
+    pub fn from_hash(weak: bool, hash: u64) -> EntityTag<'static> {
+        Self::from_hash_with_encoding(weak, hash, TagEncoding::new())
+    }
+
+    /// Construct an EntityTag from a hash value like `from_hash` does, encoding the opaque tag with the given encoding.
+    #[inline]
+    pub fn from_hash_with_encoding(
+        weak: bool,
+        hash: u64,
+        encoding: TagEncoding,
+    ) -> EntityTag<'static> {
+        EntityTag {
             weak,
-            tag: Cow::from(&opaque_tag[1..(opaque_tag.len() - 1)]),
-        })
+            tag: Cow::from(encoding.encode(&hash.to_le_bytes())),
+        }
+    }
+
+    /// Get the hash value of an entity tag generated by `from_data`, `from_file_meta` or `from_hash`, so that the compact integer can be stored instead of the text. Returns `None` if the opaque tag is not in that form.
+    ///
+    /// Note that an arbitrary opaque tag which happens to be in that form also has a hash value.
+    #[inline]
+    pub fn as_hash(&self) -> Option<u64> {
+        self.as_hash_with_encoding(TagEncoding::new())
+    }
+
+    /// Get the hash value of an entity tag generated by `from_hash_with_encoding` with the given encoding. Returns `None` if the opaque tag is not in that form.
+    #[inline]
+    pub fn as_hash_with_encoding(&self, encoding: TagEncoding) -> Option<u64> {
+        encoding.decode_hash(self.get_tag_cow())
     }
 
     /// Construct a strong EntityTag.
     #[inline]
     pub fn from_data<S: ?Sized + AsRef<[u8]>>(data: &S) -> EntityTag<'static> {
+        let data = data.as_ref();
+
+        count!("entity_tag_hashed_bytes_total", data.len());
+
         let mut hasher = HighwayHasher::default();
-        hasher.write(data.as_ref());
+        hasher.write(data);
+
+        Self::from_hash(false, salt(hasher.finish()))
+    }
 
-        let tag =
-            base64::engine::general_purpose::STANDARD_NO_PAD.encode(hasher.finish().to_le_bytes());
+    /// Construct a strong EntityTag like `from_data` does, encoding the opaque tag with the given encoding.
+    #[inline]
+    pub fn from_data_with_encoding<S: ?Sized + AsRef<[u8]>>(
+        data: &S,
+        encoding: TagEncoding,
+    ) -> EntityTag<'static> {
+        let data = data.as_ref();
+
+        count!("entity_tag_hashed_bytes_total", data.len());
+
+        let mut hasher = HighwayHasher::default();
+        hasher.write(data);
+
+        Self::from_hash_with_encoding(false, salt(hasher.finish()), encoding)
+    }
+
+    /// Construct a weak EntityTag from the CRC32C checksum and the length of the data, which is computed with the SSE4.2 or ARM CRC instructions if available. It is much cheaper than hashing, but only sufficient for weak validators.
+    #[cfg(feature = "crc32c")]
+    #[inline]
+    pub fn from_data_crc32c<S: ?Sized + AsRef<[u8]>>(data: &S) -> EntityTag<'static> {
+        let data = data.as_ref();
+
+        count!("entity_tag_hashed_bytes_total", data.len());
+
+        let mut bytes = [0u8; 12];
+
+        let mut crc = crc32c::crc32c(data);
+
+        match epoch() {
+            0 => (),
+            epoch => crc = crc32c::crc32c_append(crc, &epoch.to_le_bytes()),
+        }
+
+        bytes[..4].copy_from_slice(&crc.to_le_bytes());
+        bytes[4..].copy_from_slice(&(data.len() as u64).to_le_bytes());
 
         EntityTag {
-            weak: false, tag: Cow::from(tag)
+            weak: true, tag: Cow::from(TagEncoding::new().encode(&bytes))
         }
     }
 
+    /// Construct a strong EntityTag with SipHash-1-3 keyed by `key`, so that the opaque tag cannot be predicted without the key. The opaque tag is in the same form as the one generated by `from_data`.
+    #[cfg(feature = "siphasher")]
+    #[inline]
+    pub fn from_data_siphash13<S: ?Sized + AsRef<[u8]>>(
+        key: &[u8; 16],
+        data: &S,
+    ) -> EntityTag<'static> {
+        let data = data.as_ref();
+
+        count!("entity_tag_hashed_bytes_total", data.len());
+
+        let mut hasher = siphasher::sip::SipHasher13::new_with_key(key);
+        hasher.write(data);
+
+        Self::from_hash(false, salt(hasher.finish()))
+    }
+
+    /// Construct a strong EntityTag with SipHash-2-4 keyed by `key`, which is slower but more conservative than `from_data_siphash13`.
+    #[cfg(feature = "siphasher")]
+    #[inline]
+    pub fn from_data_siphash24<S: ?Sized + AsRef<[u8]>>(
+        key: &[u8; 16],
+        data: &S,
+    ) -> EntityTag<'static> {
+        let data = data.as_ref();
+
+        count!("entity_tag_hashed_bytes_total", data.len());
+
+        let mut hasher = siphasher::sip::SipHasher24::new_with_key(key);
+        hasher.write(data);
+
+        Self::from_hash(false, salt(hasher.finish()))
+    }
+
+    /// Construct a strong EntityTag together with a digest of the given algorithm, for the `Content-Digest` or `Repr-Digest` header field, hashing the data only once.
+    #[cfg(feature = "sha2")]
+    #[inline]
+    pub fn from_data_with_digest<S: ?Sized + AsRef<[u8]>>(
+        data: &S,
+        algorithm: digest::DigestAlgorithm,
+    ) -> (EntityTag<'static>, alloc::vec::Vec<u8>) {
+        let mut hasher = EntityTagHasher::with_digest(algorithm);
+        hasher.write(data.as_ref());
+
+        let (etag, digest) = hasher.finish_with_digest();
+
+        // the hasher always has a digest because it was created by `with_digest`
+        (etag, digest.unwrap().1)
+    }
+
     #[cfg(feature = "std")]
     /// Construct a weak EntityTag.
     pub fn from_file_meta(metadata: &Metadata) -> EntityTag<'static> {
+        Self::from_file_parts(metadata.len(), metadata.modified().ok())
+    }
+
+    #[cfg(feature = "std")]
+    /// Construct a weak EntityTag from the length and the modified time of a file, like `from_file_meta` does.
+    pub(crate) fn from_file_parts(len: u64, modified: Option<SystemTime>) -> EntityTag<'static> {
         let mut hasher = HighwayHasher::default();
 
-        hasher.write(&metadata.len().to_le_bytes());
+        hasher.write(&len.to_le_bytes());
 
-        if let Ok(modified_time) = metadata.modified() {
+        if let Some(modified_time) = modified {
             if let Ok(time) = modified_time.duration_since(UNIX_EPOCH) {
                 hasher.write(&time.as_nanos().to_le_bytes());
             } else {
@@ -285,15 +733,11 @@ impl<'t> EntityTag<'t> {
             }
         }
 
-        let tag =
-            base64::engine::general_purpose::STANDARD_NO_PAD.encode(hasher.finish().to_le_bytes());
-
-        EntityTag {
-            weak: true, tag: Cow::from(tag)
-        }
+        Self::from_hash(true, salt(hasher.finish()))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     /// Get the tag. The double quotes are not included.
     #[inline]
@@ -318,6 +762,7 @@ impl<'t> EntityTag<'t> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> EntityTag<'t> {
     /// For strong comparison two entity-tags are equivalent if both are not weak and their opaque-tags match character-by-character.
     #[inline]
@@ -342,17 +787,30 @@ impl<'t> EntityTag<'t> {
     pub fn weak_ne(&self, other: &EntityTag) -> bool {
         !self.weak_eq(other)
     }
+
+    /// Check whether a single header field value, such as `W/"abc"`, denotes the same entity tag as this one under the given comparison, without constructing another EntityTag. Surrounding whitespace is ignored, and a malformed value never matches.
+    pub fn matches_header_value<S: ?Sized + AsRef<str>>(
+        &self,
+        value: &S,
+        comparison: Comparison,
+    ) -> bool {
+        let value = value.as_ref().trim_matches(|c| c == ' ' || c == '\t');
+
+        match EntityTagRef::from_str(value) {
+            Ok(other) => match comparison {
+                Comparison::Strong => self.as_entity_tag_ref().strong_eq(&other),
+                Comparison::Weak => self.as_entity_tag_ref().weak_eq(&other),
+            },
+            Err(_) => false,
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<'t> Display for EntityTag<'t> {
+    /// Format this entity tag. The alternate flag (`{:#}`) escapes `obs-text` and `\` in the opaque tag, so that an entity tag supplied by a client can be logged safely.
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        if self.weak {
-            f.write_str("W/")?;
-        }
-
-        f.write_char('"')?;
-        f.write_str(self.tag.as_ref())?;
-        f.write_char('"')
+        buffer::fmt_entity_tag(self.weak, self.tag.as_ref(), f)
     }
 }
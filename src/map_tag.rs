@@ -0,0 +1,22 @@
+use alloc::{borrow::Cow, string::String};
+
+use crate::{EntityTag, EntityTagError};
+
+impl<'t> EntityTag<'t> {
+    /// Construct a new EntityTag whose opaque tag is the result of applying `f` to the opaque tag of this one, keeping the weakness. The result is validated, and must not be enclosed in double quotes.
+    ///
+    /// It is useful for proxies which rewrite the entity tags of upstream servers deterministically.
+    #[inline]
+    pub fn try_map_tag<F: FnOnce(&str) -> String>(
+        &self,
+        f: F,
+    ) -> Result<EntityTag<'static>, EntityTagError> {
+        let tag = f(&self.tag);
+
+        Self::check_unquoted_tag(&tag)?;
+
+        Ok(EntityTag {
+            weak: self.weak, tag: Cow::from(tag)
+        })
+    }
+}
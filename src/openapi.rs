@@ -0,0 +1,113 @@
+//! OpenAPI descriptions of entity tags and the `ETag`, `If-Match` and `If-None-Match` header fields, for [utoipa](https://docs.rs/utoipa).
+//!
+//! ```rust
+//! use entity_tag::EntityTag;
+//!
+//! #[utoipa::path(
+//!     put,
+//!     path = "/documents/{id}",
+//!     params(entity_tag::openapi::IfMatch),
+//!     responses((status = 200, headers(("ETag" = EntityTag))))
+//! )]
+//! async fn put_document() {}
+//! ```
+
+use alloc::{borrow::Cow, vec, vec::Vec};
+
+use utoipa::{
+    openapi::{
+        path::{Parameter, ParameterBuilder, ParameterIn},
+        schema::{ObjectBuilder, Schema, Type},
+        Header, HeaderBuilder, RefOr, Required,
+    },
+    IntoParams, PartialSchema, ToSchema,
+};
+
+use crate::{EntityTag, EntityTagList, ENTITY_TAG_PATTERN};
+
+impl<'t> PartialSchema for EntityTag<'t> {
+    #[inline]
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .pattern(Some(ENTITY_TAG_PATTERN))
+            .description(Some("An entity tag, such as `\"xyzzy\"` or `W/\"xyzzy\"`."))
+            .into()
+    }
+}
+
+impl<'t> ToSchema for EntityTag<'t> {
+    #[inline]
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("EntityTag")
+    }
+}
+
+impl<'t> PartialSchema for EntityTagList<'t> {
+    #[inline]
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(
+                "`*` or a comma-separated list of entity tags, such as `\"xyzzy\", \
+                 W/\"r2d2xxxx\"`.",
+            ))
+            .into()
+    }
+}
+
+impl<'t> ToSchema for EntityTagList<'t> {
+    #[inline]
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("EntityTagList")
+    }
+}
+
+/// The `If-Match` request header parameter, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.1).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct IfMatch;
+
+/// The `If-None-Match` request header parameter, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.2).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct IfNoneMatch;
+
+fn list_parameter(name: &str, description: &str) -> Parameter {
+    ParameterBuilder::new()
+        .name(name)
+        .parameter_in(ParameterIn::Header)
+        .required(Required::False)
+        .description(Some(description))
+        .schema(Some(EntityTagList::schema()))
+        .build()
+}
+
+impl IntoParams for IfMatch {
+    #[inline]
+    fn into_params(_parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        vec![list_parameter(
+            EntityTag::IF_MATCH_HEADER_NAME,
+            "Perform the request only if the current entity tag of the resource matches one of \
+             the listed entity tags.",
+        )]
+    }
+}
+
+impl IntoParams for IfNoneMatch {
+    #[inline]
+    fn into_params(_parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        vec![list_parameter(
+            EntityTag::IF_NONE_MATCH_HEADER_NAME,
+            "Perform the request only if the current entity tag of the resource matches none of \
+             the listed entity tags.",
+        )]
+    }
+}
+
+/// The `ETag` response header, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3).
+#[inline]
+pub fn etag_header() -> Header {
+    HeaderBuilder::new()
+        .schema(EntityTag::schema())
+        .description(Some("The entity tag of the selected representation."))
+        .build()
+}
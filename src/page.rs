@@ -0,0 +1,45 @@
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use highway::HighwayHasher;
+
+use crate::{epoch::salt, EntityTag};
+
+/// Feed bytes into a hasher with their length, so that adjacent values cannot be confused.
+#[inline]
+fn write_bytes(hasher: &mut HighwayHasher, bytes: &[u8]) {
+    hasher.write(&(bytes.len() as u64).to_le_bytes());
+    hasher.write(bytes);
+}
+
+impl<'t> EntityTag<'t> {
+    /// Construct an EntityTag for a page of a paginated collection from the entity tag of the whole collection, the page number, the page size and the other query parameters which shape the page, such as sorting and filtering parameters.
+    ///
+    /// The entity tag changes when either the collection or the shape of the query changes. The order of the query parameters does not matter, but the order of the values of a repeated parameter does. It is weak if the entity tag of the collection is weak.
+    pub fn from_page<K: AsRef<str>, V: AsRef<str>>(
+        collection: &EntityTag,
+        page: u64,
+        page_size: u64,
+        params: &[(K, V)],
+    ) -> EntityTag<'static> {
+        let mut params: Vec<(&str, &str)> =
+            params.iter().map(|(key, value)| (key.as_ref(), value.as_ref())).collect();
+
+        // a stable sort keeps the order of the values of a repeated parameter
+        params.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = HighwayHasher::default();
+
+        hasher.write(b"page");
+        write_bytes(&mut hasher, collection.get_tag_cow().as_bytes());
+        hasher.write(&page.to_le_bytes());
+        hasher.write(&page_size.to_le_bytes());
+
+        for (key, value) in params {
+            write_bytes(&mut hasher, key.as_bytes());
+            write_bytes(&mut hasher, value.as_bytes());
+        }
+
+        Self::from_hash(collection.weak, salt(hasher.finish()))
+    }
+}
@@ -0,0 +1,64 @@
+/// Opt-in relaxations of the `entity-tag` grammar for parsing entity tags emitted by non-conforming origins.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ParseOptions {
+    pub(crate) lowercase_weak: bool,
+    pub(crate) trim_ows:       bool,
+    pub(crate) missing_quotes: bool,
+}
+
+impl ParseOptions {
+    /// Create options which follow the grammar strictly, like `from_str` does.
+    #[inline]
+    pub const fn new() -> Self {
+        ParseOptions {
+            lowercase_weak: false, trim_ows: false, missing_quotes: false
+        }
+    }
+
+    /// Create options which accept every relaxation, for canonicalizing sloppily formatted entity tags.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) const fn lenient() -> Self {
+        ParseOptions {
+            lowercase_weak: true, trim_ows: true, missing_quotes: true
+        }
+    }
+
+    /// Whether to accept the lowercase weakness indicator `w/`. It is always formatted as `W/`.
+    #[inline]
+    pub const fn lowercase_weak(mut self, accept: bool) -> Self {
+        self.lowercase_weak = accept;
+
+        self
+    }
+
+    /// Whether to remove the optional whitespace (spaces and horizontal tabs) around the entity tag, which is often left by generic header splitting, such as ` "xyzzy"`.
+    #[inline]
+    pub const fn trim_ows(mut self, trim: bool) -> Self {
+        self.trim_ows = trim;
+
+        self
+    }
+
+    /// Whether to accept an opaque tag without the surrounding double quotes, such as `xyzzy`. It is always formatted with them.
+    #[inline]
+    pub const fn missing_quotes(mut self, accept: bool) -> Self {
+        self.missing_quotes = accept;
+
+        self
+    }
+
+    /// Remove the optional whitespace around `s` if it is enabled, and split the weakness indicator off `s`.
+    #[inline]
+    pub(crate) fn strip_weak(self, s: &str) -> (bool, &str) {
+        let s = if self.trim_ows { s.trim_matches(|c| c == ' ' || c == '\t') } else { s };
+
+        if let Some(s) = s.strip_prefix("W/") {
+            (true, s)
+        } else if let Some(s) = s.strip_prefix("w/").filter(|_| self.lowercase_weak) {
+            (true, s)
+        } else {
+            (false, s)
+        }
+    }
+}
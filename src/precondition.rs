@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use crate::{EntityTag, EntityTagList, PreconditionError, PreconditionStep};
+
+/// A precondition on a resource, carried by either `If-Match` or `If-None-Match`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Precondition<'t> {
+    /// `If-Match`
+    IfMatch(EntityTagList<'t>),
+    /// `If-None-Match`
+    IfNoneMatch(EntityTagList<'t>),
+}
+
+impl<'t> Precondition<'t> {
+    /// Evaluate the precondition. `current` is the entity tag of the selected representation, or `None` if the target resource has no current representation.
+    #[inline]
+    pub fn check(&self, current: Option<&EntityTag>) -> Result<(), PreconditionError> {
+        match self {
+            Precondition::IfMatch(list) => list.check_if_match(current),
+            Precondition::IfNoneMatch(list) => list.check_if_none_match(current),
+        }
+    }
+
+    /// Evaluate the precondition like `check` does, and explain how it was evaluated.
+    #[inline]
+    pub fn trace(&self, current: Option<&EntityTag>) -> PreconditionStep {
+        match self {
+            Precondition::IfMatch(list) => list.trace_if_match(current),
+            Precondition::IfNoneMatch(list) => list.trace_if_none_match(current),
+        }
+    }
+
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> Precondition<'static> {
+        match self {
+            Precondition::IfMatch(list) => Precondition::IfMatch(list.into_owned()),
+            Precondition::IfNoneMatch(list) => Precondition::IfNoneMatch(list.into_owned()),
+        }
+    }
+}
+
+impl<'t> EntityTag<'t> {
+    /// Whether the `If-Match` precondition passes for this entity tag of the selected representation, given the raw field value, such as `"a", "b"` or `*`. A malformed field value never matches.
+    #[inline]
+    pub fn satisfies_if_match<S: ?Sized + AsRef<str>>(&self, if_match: &S) -> bool {
+        match EntityTagList::from_str(if_match) {
+            Ok(list) => list.check_if_match(Some(self)).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether the `If-None-Match` precondition passes for this entity tag of the selected representation, given the raw field value, such as `W/"a", "b"` or `*`. A malformed field value is ignored, so the precondition passes.
+    #[inline]
+    pub fn satisfies_if_none_match<S: ?Sized + AsRef<str>>(&self, if_none_match: &S) -> bool {
+        match EntityTagList::from_str(if_none_match) {
+            Ok(list) => list.check_if_none_match(Some(self)).is_ok(),
+            Err(_) => true,
+        }
+    }
+}
+
+/// The outcomes of evaluating the preconditions of several resources with `evaluate_batch`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BatchOutcome<R> {
+    outcomes: Vec<(R, Result<(), PreconditionError>)>,
+}
+
+impl<R> BatchOutcome<R> {
+    /// Whether the preconditions of all the resources are satisfied, which means the whole operation may proceed.
+    #[inline]
+    pub fn is_satisfied(&self) -> bool {
+        self.outcomes.iter().all(|(_, outcome)| outcome.is_ok())
+    }
+
+    /// Get the outcome of every resource, in the order they were given.
+    #[inline]
+    pub fn outcomes(&self) -> &[(R, Result<(), PreconditionError>)] {
+        &self.outcomes
+    }
+
+    /// Iterate over the resources whose preconditions failed.
+    #[inline]
+    pub fn failures(&self) -> impl Iterator<Item = (&R, &PreconditionError)> {
+        self.outcomes.iter().filter_map(|(resource, outcome)| match outcome {
+            Ok(()) => None,
+            Err(error) => Some((resource, error)),
+        })
+    }
+
+    /// Into the outcome of every resource, in the order they were given.
+    #[inline]
+    pub fn into_outcomes(self) -> Vec<(R, Result<(), PreconditionError>)> {
+        self.outcomes
+    }
+}
+
+/// Evaluate the preconditions of several resources, given as `(resource, current entity tag, precondition)` tuples, for an operation which either applies to all of them or to none of them (e.g. a batch `PATCH`).
+///
+/// Every precondition is evaluated even if an earlier one has failed, so that the outcome of each resource can be reported. The operation should only proceed if `BatchOutcome::is_satisfied` returns `true`. The caller is responsible for keeping the current entity tags from changing until the operation completes.
+pub fn evaluate_batch<'a, 't, 'p, R, I>(items: I) -> BatchOutcome<R>
+where
+    't: 'a,
+    'p: 'a,
+    I: IntoIterator<Item = (R, Option<&'a EntityTag<'t>>, &'a Precondition<'p>)>, {
+    let outcomes = items
+        .into_iter()
+        .map(|(resource, current, precondition)| (resource, precondition.check(current)))
+        .collect();
+
+    BatchOutcome {
+        outcomes,
+    }
+}
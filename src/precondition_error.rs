@@ -0,0 +1,188 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::EntityTag;
+
+/// A header field carrying a precondition.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PreconditionHeader {
+    /// `If-Match`
+    IfMatch,
+    /// `If-None-Match`
+    IfNoneMatch,
+}
+
+impl PreconditionHeader {
+    /// Get the name of the header field.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            PreconditionHeader::IfMatch => EntityTag::IF_MATCH_HEADER_NAME,
+            PreconditionHeader::IfNoneMatch => EntityTag::IF_NONE_MATCH_HEADER_NAME,
+        }
+    }
+}
+
+impl Display for PreconditionHeader {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The reason why a precondition evaluated to false.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PreconditionFailure {
+    /// `*` is given but the target resource has no current representation.
+    NoRepresentation,
+    /// `*` is given and the target resource has a current representation.
+    RepresentationExists,
+    /// None of the given entity tags matches the current one.
+    NoneMatched,
+    /// One of the given entity tags matches the current one.
+    Matched,
+}
+
+impl Display for PreconditionFailure {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            PreconditionFailure::NoRepresentation => {
+                f.write_str("the target resource has no current representation")
+            },
+            PreconditionFailure::RepresentationExists => {
+                f.write_str("the target resource has a current representation")
+            },
+            PreconditionFailure::NoneMatched => {
+                f.write_str("no entity tag matches the current representation")
+            },
+            PreconditionFailure::Matched => {
+                f.write_str("an entity tag matches the current representation")
+            },
+        }
+    }
+}
+
+/// A precondition which evaluated to false.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PreconditionError {
+    header:       PreconditionHeader,
+    failure:      PreconditionFailure,
+    not_modified: bool,
+}
+
+impl PreconditionError {
+    #[inline]
+    pub(crate) const fn new(header: PreconditionHeader, failure: PreconditionFailure) -> Self {
+        PreconditionError {
+            header,
+            failure,
+            not_modified: false,
+        }
+    }
+
+    #[cfg(feature = "http")]
+    #[inline]
+    pub(crate) const fn into_not_modified(mut self) -> Self {
+        self.not_modified = true;
+
+        self
+    }
+
+    /// Get the header field whose precondition failed.
+    #[inline]
+    pub const fn header(&self) -> PreconditionHeader {
+        self.header
+    }
+
+    /// Get the reason why the precondition failed.
+    #[inline]
+    pub const fn failure(&self) -> PreconditionFailure {
+        self.failure
+    }
+
+    /// Whether the request should be answered with `304 Not Modified` rather than `412 Precondition Failed`. This is only the case for a failed `If-None-Match` of a `GET` or `HEAD` request.
+    #[inline]
+    pub const fn is_not_modified(&self) -> bool {
+        self.not_modified
+    }
+}
+
+impl Display for PreconditionError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("the precondition of ")?;
+        f.write_str(self.header.as_str())?;
+        f.write_str(" failed: ")?;
+        Display::fmt(&self.failure, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PreconditionError {}
+
+#[cfg(feature = "http")]
+impl PreconditionHeader {
+    /// Get the name of the header field as a `HeaderName`.
+    #[inline]
+    pub const fn header_name(&self) -> http::HeaderName {
+        match self {
+            PreconditionHeader::IfMatch => http::header::IF_MATCH,
+            PreconditionHeader::IfNoneMatch => http::header::IF_NONE_MATCH,
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl PreconditionError {
+    /// Get the status code of the response to the request.
+    #[inline]
+    pub const fn status_code(&self) -> http::StatusCode {
+        if self.not_modified {
+            http::StatusCode::NOT_MODIFIED
+        } else {
+            http::StatusCode::PRECONDITION_FAILED
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum_core::response::IntoResponse for PreconditionError {
+    #[inline]
+    fn into_response(self) -> axum_core::response::Response {
+        use alloc::string::ToString;
+
+        if self.not_modified {
+            self.status_code().into_response()
+        } else {
+            (self.status_code(), self.to_string()).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "actix-web")]
+impl actix_web::ResponseError for PreconditionError {
+    #[inline]
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        if self.not_modified {
+            actix_web::http::StatusCode::NOT_MODIFIED
+        } else {
+            actix_web::http::StatusCode::PRECONDITION_FAILED
+        }
+    }
+
+    #[inline]
+    fn error_response(&self) -> actix_web::HttpResponse {
+        use alloc::string::ToString;
+
+        let mut builder =
+            actix_web::HttpResponse::build(actix_web::ResponseError::status_code(self));
+
+        if self.not_modified {
+            builder.finish()
+        } else {
+            builder.body(self.to_string())
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{EntityTagListItem, PreconditionError, PreconditionHeader};
+
+/// A comparison function for entity tags, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3.2).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Comparison {
+    /// The strong comparison, used by `If-Match`.
+    Strong,
+    /// The weak comparison, used by `If-None-Match`.
+    Weak,
+}
+
+impl Display for Comparison {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Comparison::Strong => f.write_str("strong comparison"),
+            Comparison::Weak => f.write_str("weak comparison"),
+        }
+    }
+}
+
+/// An explanation of how the precondition of one header field was evaluated, for diagnostics such as the body of a `412 Precondition Failed` response.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PreconditionStep {
+    header:    PreconditionHeader,
+    malformed: bool,
+    matched:   Option<EntityTagListItem<'static>>,
+    outcome:   Result<(), PreconditionError>,
+}
+
+impl PreconditionStep {
+    #[inline]
+    pub(crate) fn new(
+        header: PreconditionHeader,
+        matched: Option<EntityTagListItem<'static>>,
+        outcome: Result<(), PreconditionError>,
+    ) -> Self {
+        PreconditionStep {
+            header,
+            malformed: false,
+            matched,
+            outcome,
+        }
+    }
+
+    #[cfg(feature = "http")]
+    #[inline]
+    pub(crate) fn malformed(
+        header: PreconditionHeader,
+        outcome: Result<(), PreconditionError>,
+    ) -> Self {
+        PreconditionStep {
+            header,
+            malformed: true,
+            matched: None,
+            outcome,
+        }
+    }
+
+    /// Get the header field which was evaluated.
+    #[inline]
+    pub const fn header(&self) -> PreconditionHeader {
+        self.header
+    }
+
+    /// Get the comparison function used for the header field. `If-Match` uses the strong comparison and `If-None-Match` uses the weak comparison.
+    #[inline]
+    pub const fn comparison(&self) -> Comparison {
+        match self.header {
+            PreconditionHeader::IfMatch => Comparison::Strong,
+            PreconditionHeader::IfNoneMatch => Comparison::Weak,
+        }
+    }
+
+    /// Whether the field value is malformed, in which case no comparison ran.
+    #[inline]
+    pub const fn is_malformed(&self) -> bool {
+        self.malformed
+    }
+
+    /// Get the element of the field value which matched the current entity tag, if any. `*` matches any current representation.
+    #[inline]
+    pub const fn matched(&self) -> Option<&EntityTagListItem<'static>> {
+        self.matched.as_ref()
+    }
+
+    /// Get the outcome of the precondition.
+    #[inline]
+    pub const fn outcome(&self) -> Result<(), PreconditionError> {
+        self.outcome
+    }
+}
+
+impl Display for PreconditionStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.header.as_str())?;
+
+        if self.malformed {
+            f.write_str(": the field value is malformed")?;
+        } else {
+            write!(f, ": {}, ", self.comparison())?;
+
+            match &self.matched {
+                Some(EntityTagListItem::Any) => f.write_str("`*` matched")?,
+                Some(EntityTagListItem::Tag(etag)) => write!(f, "{} matched", etag)?,
+                None => f.write_str("nothing matched")?,
+            }
+        }
+
+        match &self.outcome {
+            Ok(()) => f.write_str(", passed"),
+            Err(error) => write!(f, ", failed because {}", error.failure()),
+        }
+    }
+}
+
+/// An explanation of how the preconditions of a request were evaluated, consisting of a step for every header field which was present, in the order of evaluation.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PreconditionTrace {
+    steps: Vec<PreconditionStep>,
+}
+
+impl PreconditionTrace {
+    /// Record a step and return its outcome.
+    #[cfg(feature = "http")]
+    #[inline]
+    pub(crate) fn record(&mut self, step: PreconditionStep) -> Result<(), PreconditionError> {
+        let outcome = step.outcome;
+
+        self.steps.push(step);
+
+        outcome
+    }
+
+    /// Turn the failure of the last step into a `304 Not Modified` one.
+    #[cfg(feature = "http")]
+    #[inline]
+    pub(crate) fn set_not_modified(&mut self) {
+        if let Some(step) = self.steps.last_mut() {
+            step.outcome = step.outcome.map_err(PreconditionError::into_not_modified);
+        }
+    }
+
+    /// Get the steps of the evaluation.
+    #[inline]
+    pub fn steps(&self) -> &[PreconditionStep] {
+        &self.steps
+    }
+}
+
+impl Display for PreconditionTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.steps.is_empty() {
+            return f.write_str("no precondition");
+        }
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            Display::fmt(step, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromIterator<PreconditionStep> for PreconditionTrace {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = PreconditionStep>>(iter: I) -> Self {
+        PreconditionTrace {
+            steps: iter.into_iter().collect()
+        }
+    }
+}
+
+impl Extend<PreconditionStep> for PreconditionTrace {
+    #[inline]
+    fn extend<I: IntoIterator<Item = PreconditionStep>>(&mut self, iter: I) {
+        self.steps.extend(iter);
+    }
+}
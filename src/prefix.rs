@@ -0,0 +1,35 @@
+use alloc::{borrow::Cow, string::String};
+
+use crate::{EntityTag, EntityTagError};
+
+impl<'t> EntityTag<'t> {
+    /// Construct a new EntityTag whose opaque tag is this one prepended with `prefix`, such as `v2-` for an API version or a tenant, keeping the weakness. The prefix must only consist of characters allowed in an opaque tag.
+    pub fn with_prefix<S: ?Sized + AsRef<str>>(
+        &self,
+        prefix: &S,
+    ) -> Result<EntityTag<'static>, EntityTagError> {
+        let prefix = prefix.as_ref();
+
+        Self::check_unquoted_tag(prefix)?;
+
+        let mut tag = String::with_capacity(prefix.len() + self.tag.len());
+
+        tag.push_str(prefix);
+        tag.push_str(&self.tag);
+
+        Ok(EntityTag {
+            weak: self.weak, tag: Cow::from(tag)
+        })
+    }
+
+    /// Construct a new EntityTag which borrows the opaque tag of this one without `prefix`, keeping the weakness. Returns `None` if the opaque tag does not start with `prefix`.
+    #[inline]
+    pub fn strip_prefix<S: ?Sized + AsRef<str>>(&self, prefix: &S) -> Option<EntityTag<'_>> {
+        // the rest of a valid opaque tag is always valid
+        self.tag
+            .strip_prefix(prefix.as_ref())
+            .map(|tag| EntityTag {
+                weak: self.weak, tag: Cow::from(tag)
+            })
+    }
+}
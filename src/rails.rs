@@ -0,0 +1,57 @@
+use alloc::borrow::Cow;
+
+use crate::EntityTag;
+
+/// The digest class used by `ActiveSupport::Digest`, configured by `config.active_support.hash_digest_class`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum RailsDigest {
+    /// The default before Rails 5.2.
+    #[cfg(feature = "md-5")]
+    Md5,
+    /// The default from Rails 5.2 to Rails 6.1.
+    #[cfg(feature = "sha1")]
+    Sha1,
+    /// The default since Rails 7.0.
+    #[cfg(feature = "sha2")]
+    Sha256,
+}
+
+impl<'t> EntityTag<'t> {
+    /// Construct the weak EntityTag which Rails generates for a cache key, e.g. by `fresh_when(etag: key)`. If the key consists of several parts (such as a record and a template digest), they should be joined with `/` in advance, as `ActiveSupport::Cache.expand_cache_key` does.
+    ///
+    /// The `RAILS_CACHE_ID` and `RAILS_APP_VERSION` environment variables, which Rails puts in front of the key if they are set, are not taken into account.
+    pub fn from_rails_cache_key<S: ?Sized + AsRef<str>>(
+        key: &S,
+        digest: RailsDigest,
+    ) -> EntityTag<'static> {
+        let key = key.as_ref();
+
+        let mut tag = match digest {
+            #[cfg(feature = "md-5")]
+            RailsDigest::Md5 => {
+                use md5::{Digest, Md5};
+
+                crate::encode_hex(&Md5::digest(key))
+            },
+            #[cfg(feature = "sha1")]
+            RailsDigest::Sha1 => {
+                use sha1::{Digest, Sha1};
+
+                crate::encode_hex(&Sha1::digest(key))
+            },
+            #[cfg(feature = "sha2")]
+            RailsDigest::Sha256 => {
+                use sha2::{Digest, Sha256};
+
+                crate::encode_hex(&Sha256::digest(key))
+            },
+        };
+
+        // `ActiveSupport::Digest.hexdigest` truncates the digest to 32 characters
+        tag.truncate(32);
+
+        EntityTag {
+            weak: true, tag: Cow::from(tag)
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::{string::String, vec::Vec};
+
+use redis::{aio::ConnectionLike, RedisResult, Script};
+
+use crate::{tag_storage::decode_value, EntityTag};
+
+/// Set `KEYS[1]` to `ARGV[2]` only if its value is `ARGV[1]`, where an empty string stands for a missing key. The binary form of an entity tag is never empty.
+const REPLACE_SCRIPT: &str = r"
+local current = redis.call('GET', KEYS[1])
+
+if (current or '') == ARGV[1] then
+    redis.call('SET', KEYS[1], ARGV[2])
+
+    return 1
+end
+
+return 0
+";
+
+/// An asynchronous storage of the current entity tags of resources in Redis, so that a fleet of stateless servers shares one source of truth and evaluates `If-Match` preconditions consistently.
+///
+/// The entity tags are stored in the form of `EntityTag::to_bytes` under the keys of the resources with a prefix, which is `etag:` by default.
+#[derive(Debug, Clone)]
+pub struct RedisTagStorage<C> {
+    connection: C,
+    prefix:     String,
+    replace:    Script,
+}
+
+impl<C: ConnectionLike> RedisTagStorage<C> {
+    /// Wrap a connection, such as a `redis::aio::ConnectionManager`.
+    #[inline]
+    pub fn new(connection: C) -> Self {
+        Self::with_prefix(connection, "etag:")
+    }
+
+    /// Wrap a connection and put the given prefix before the keys of the resources.
+    #[inline]
+    pub fn with_prefix<P: Into<String>>(connection: C, prefix: P) -> Self {
+        RedisTagStorage {
+            connection,
+            prefix: prefix.into(),
+            replace: Script::new(REPLACE_SCRIPT),
+        }
+    }
+
+    /// Get the prefix of the keys.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    /// Get a mutable reference to the connection.
+    #[inline]
+    pub fn connection_mut(&mut self) -> &mut C {
+        &mut self.connection
+    }
+
+    #[inline]
+    fn redis_key(&self, key: &str) -> String {
+        let mut redis_key = String::with_capacity(self.prefix.len() + key.len());
+
+        redis_key.push_str(&self.prefix);
+        redis_key.push_str(key);
+
+        redis_key
+    }
+
+    /// Get the entity tag stored with the key. Returns `Ok(None)` if there is no such entity tag or the stored value is malformed.
+    pub async fn get_tag(&mut self, key: &str) -> RedisResult<Option<EntityTag<'static>>> {
+        let value: Option<Vec<u8>> =
+            redis::cmd("GET").arg(self.redis_key(key)).query_async(&mut self.connection).await?;
+
+        Ok(value.and_then(|value| decode_value(key, &value)))
+    }
+
+    /// Store an entity tag with the key, replacing the existing one.
+    pub async fn set_tag(&mut self, key: &str, etag: &EntityTag<'_>) -> RedisResult<()> {
+        redis::cmd("SET")
+            .arg(self.redis_key(key))
+            .arg(etag.to_bytes())
+            .query_async(&mut self.connection)
+            .await
+    }
+
+    /// Remove the entity tag stored with the key, if any.
+    pub async fn remove_tag(&mut self, key: &str) -> RedisResult<()> {
+        redis::cmd("DEL").arg(self.redis_key(key)).query_async(&mut self.connection).await
+    }
+
+    /// Store an entity tag with the key only if the stored entity tag is still identical to `current` (`None` means that nothing is stored), atomically across all the servers sharing the storage. Returns whether the entity tag was stored.
+    ///
+    /// This lets a server which has passed an `If-Match` precondition update the resource without losing a concurrent update made through another server.
+    pub async fn replace_tag(
+        &mut self,
+        key: &str,
+        current: Option<&EntityTag<'_>>,
+        etag: &EntityTag<'_>,
+    ) -> RedisResult<bool> {
+        let current = current.map(EntityTag::to_bytes).unwrap_or_default();
+
+        self.replace
+            .key(self.redis_key(key))
+            .arg(current)
+            .arg(etag.to_bytes())
+            .invoke_async(&mut self.connection)
+            .await
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::{EntityTag, RowVersionTag};
+
+/// A version of a resource, such as a monotonic counter or a timestamp stored in a version column, which is used as the validator of the resource.
+///
+/// It is rendered as a strong entity tag whose opaque tag is the version in lowercase hexadecimal digits, the same as `RowVersionTag` does, and can be parsed back from an entity tag presented in `If-Match`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ResourceVersion(u64);
+
+impl ResourceVersion {
+    /// Create a version from a counter.
+    #[inline]
+    pub const fn new(version: u64) -> Self {
+        ResourceVersion(version)
+    }
+
+    /// Create a version from the current time, in nanoseconds since the UNIX epoch.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn now() -> Self {
+        ResourceVersion(SystemTime::now().row_version())
+    }
+
+    /// Get the version.
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Increase the version by one and return the new version. The version saturates at `u64::MAX`.
+    #[inline]
+    pub fn bump(&mut self) -> Self {
+        self.0 = self.0.saturating_add(1);
+
+        *self
+    }
+
+    /// Set the version to the current time, or increase it by one if the clock has not passed it, so that the version still increases monotonically when the clock goes backwards. Return the new version.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn bump_to_now(&mut self) -> Self {
+        self.0 = Self::now().0.max(self.0.saturating_add(1));
+
+        *self
+    }
+
+    /// Parse a version from an entity tag rendered by `to_entity_tag`. Returns `None` if the entity tag is weak or its opaque tag is not a version in the canonical form.
+    pub fn from_entity_tag(etag: &EntityTag) -> Option<Self> {
+        if etag.weak {
+            return None;
+        }
+
+        let tag = etag.get_tag_cow().as_ref();
+
+        // only the form rendered by `to_entity_tag` is accepted, so that a version has only one entity tag
+        let canonical = !tag.is_empty()
+            && tag.len() <= 16
+            && (tag == "0" || !tag.starts_with('0'))
+            && tag.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+
+        if !canonical {
+            return None;
+        }
+
+        u64::from_str_radix(tag, 16).ok().map(ResourceVersion)
+    }
+}
+
+impl RowVersionTag for ResourceVersion {
+    #[inline]
+    fn row_version(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ResourceVersion {
+    #[inline]
+    fn from(version: u64) -> Self {
+        ResourceVersion(version)
+    }
+}
+
+impl From<ResourceVersion> for u64 {
+    #[inline]
+    fn from(version: ResourceVersion) -> Self {
+        version.0
+    }
+}
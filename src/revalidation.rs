@@ -0,0 +1,90 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::{EntityTag, EntityTagList};
+
+/// The conditional header fields of a request revalidating a stored response, defined in [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-4.3.1), for HTTP client caches.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Revalidation<'t> {
+    etags:         Vec<EntityTag<'t>>,
+    last_modified: Option<Cow<'t, str>>,
+}
+
+impl<'t> Revalidation<'t> {
+    /// Create a revalidation without any stored validator.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the entity tag of a stored response to `If-None-Match`. Weak entity tags can be used as well because the weak comparison is used.
+    #[inline]
+    pub fn etag(mut self, etag: EntityTag<'t>) -> Self {
+        self.etags.push(etag);
+
+        self
+    }
+
+    /// Set `If-Modified-Since` to the `Last-Modified` field value of the stored response, which is sent as it is.
+    #[inline]
+    pub fn last_modified<S: Into<Cow<'t, str>>>(mut self, last_modified: S) -> Self {
+        self.last_modified = Some(last_modified.into());
+
+        self
+    }
+
+    /// Set `If-Modified-Since` to the last modified time of the stored response.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn last_modified_time(self, time: SystemTime) -> Self {
+        self.last_modified(crate::http_date::format_http_date(time))
+    }
+
+    /// Whether there is any validator, that is, whether the request would be conditional.
+    #[inline]
+    pub fn is_conditional(&self) -> bool {
+        !self.etags.is_empty() || self.last_modified.is_some()
+    }
+
+    /// Build the conditional header fields as pairs of lowercase field names and field values. `If-None-Match` is present if there is any entity tag, and `If-Modified-Since` is present if the last modified time is set.
+    ///
+    /// A recipient ignores `If-Modified-Since` when `If-None-Match` is present, but sending both lets caches which do not support entity tags revalidate as well.
+    pub fn to_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::with_capacity(2);
+
+        let list: Option<EntityTagList> = self.etags.iter().cloned().collect();
+
+        if let Some(list) = list {
+            headers.push(("if-none-match", list.to_string()));
+        }
+
+        if let Some(last_modified) = self.last_modified.as_ref() {
+            headers.push(("if-modified-since", String::from(last_modified.as_ref())));
+        }
+
+        headers
+    }
+
+    /// Insert the conditional header fields into a `HeaderMap`, replacing the existing ones. A field value which is not a valid header value, such as an entity tag made by an unchecked constructor with CR or LF, is skipped.
+    #[cfg(feature = "http")]
+    pub fn apply_to(&self, headers: &mut http::HeaderMap) {
+        use core::convert::TryFrom;
+
+        let list: Option<EntityTagList> = self.etags.iter().cloned().collect();
+
+        if let Some(Ok(value)) = list.as_ref().map(http::HeaderValue::try_from) {
+            headers.insert(http::header::IF_NONE_MATCH, value);
+        }
+
+        if let Some(last_modified) = self.last_modified.as_ref() {
+            if let Ok(value) = http::HeaderValue::from_str(last_modified) {
+                headers.insert(http::header::IF_MODIFIED_SINCE, value);
+            }
+        }
+    }
+}
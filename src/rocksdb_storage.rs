@@ -0,0 +1,22 @@
+use rocksdb::{DBWithThreadMode, ThreadMode};
+
+use crate::{tag_storage::decode_value, EntityTag, TagStorage};
+
+impl<T: ThreadMode> TagStorage for DBWithThreadMode<T> {
+    type Error = rocksdb::Error;
+
+    #[inline]
+    fn get_tag(&mut self, key: &str) -> Result<Option<EntityTag<'static>>, Self::Error> {
+        Ok(self.get_pinned(key)?.and_then(|value| decode_value(key, &value)))
+    }
+
+    #[inline]
+    fn set_tag(&mut self, key: &str, etag: &EntityTag) -> Result<(), Self::Error> {
+        self.put(key, etag.to_bytes())
+    }
+
+    #[inline]
+    fn remove_tag(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.delete(key)
+    }
+}
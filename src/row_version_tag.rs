@@ -0,0 +1,57 @@
+use alloc::{borrow::Cow, format};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::EntityTag;
+
+/// Database row versions, such as `xmin`-style counters, `rowversion` columns and `updated_at` timestamps, which can be turned into entity tags.
+///
+/// Every row version is reduced to a 64-bit value whose lowercase hexadecimal digits become the opaque tag of a strong entity tag. Therefore equal versions result in the same entity tag no matter which type they are stored in.
+pub trait RowVersionTag {
+    /// Reduce this row version to a 64-bit value.
+    fn row_version(&self) -> u64;
+
+    /// Construct a strong EntityTag from this row version.
+    #[inline]
+    fn to_entity_tag(&self) -> EntityTag<'static> {
+        EntityTag {
+            weak: false, tag: Cow::from(format!("{:x}", self.row_version()))
+        }
+    }
+}
+
+impl RowVersionTag for u32 {
+    #[inline]
+    fn row_version(&self) -> u64 {
+        u64::from(*self)
+    }
+}
+
+impl RowVersionTag for u64 {
+    #[inline]
+    fn row_version(&self) -> u64 {
+        *self
+    }
+}
+
+impl RowVersionTag for i64 {
+    /// The bits of the two's complement representation are used.
+    #[inline]
+    fn row_version(&self) -> u64 {
+        *self as u64
+    }
+}
+
+#[cfg(feature = "std")]
+impl RowVersionTag for SystemTime {
+    /// The nanoseconds since the UNIX epoch (negative for earlier times, saturating at the range of `i64`) are used like an `i64` row version.
+    #[inline]
+    fn row_version(&self) -> u64 {
+        let nanos = match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => i64::try_from(d.as_nanos()).unwrap_or(i64::MAX),
+            Err(e) => i64::try_from(e.duration().as_nanos()).map(|n| -n).unwrap_or(i64::MIN),
+        };
+
+        nanos.row_version()
+    }
+}
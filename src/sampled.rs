@@ -0,0 +1,85 @@
+use core::{cmp::min, hash::Hasher};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Seek, SeekFrom};
+
+use highway::HighwayHasher;
+
+use crate::{epoch::salt, EntityTag};
+
+/// Start hashing sampled data of the given length. The marker keeps the result apart from the other generated entity tags.
+#[inline]
+fn sampled_hasher(len: u64) -> HighwayHasher {
+    let mut hasher = HighwayHasher::default();
+
+    hasher.write(b"sampled");
+    hasher.write(&len.to_le_bytes());
+
+    hasher
+}
+
+/// Get the ranges of the head and the tail of data of the given length. They never overlap.
+#[inline]
+fn sample_ranges(len: u64, sample_len: u64) -> (u64, u64) {
+    let head = min(len, sample_len);
+    let tail = min(len - head, sample_len);
+
+    (head, tail)
+}
+
+impl<'t> EntityTag<'t> {
+    /// Construct a weak EntityTag by hashing only the length, the first `sample_len` bytes and the last `sample_len` bytes of the data, like the quick check of rsync, for huge media files whose full hashing is too expensive.
+    ///
+    /// The entity tag does not change when only the middle of the data changes, so it is always weak. Use it only when such a change is accompanied by a change of the head, the tail or the length.
+    pub fn from_data_sampled<S: ?Sized + AsRef<[u8]>>(
+        data: &S,
+        sample_len: u64,
+    ) -> EntityTag<'static> {
+        let data = data.as_ref();
+        let len = data.len() as u64;
+
+        let (head, tail) = sample_ranges(len, sample_len);
+
+        count!("entity_tag_hashed_bytes_total", head + tail);
+
+        let mut hasher = sampled_hasher(len);
+
+        hasher.write(&data[..head as usize]);
+        hasher.write(&data[(len - tail) as usize..]);
+
+        Self::from_hash(true, salt(hasher.finish()))
+    }
+
+    /// Construct a weak EntityTag by reading only the first `sample_len` bytes and the last `sample_len` bytes of a file or another seekable reader, like `from_data_sampled` does. The reader is read from its start and is left at an unspecified position.
+    #[cfg(feature = "std")]
+    pub fn from_reader_sampled<R: Read + Seek>(
+        reader: &mut R,
+        sample_len: u64,
+    ) -> io::Result<EntityTag<'static>> {
+        let len = reader.seek(SeekFrom::End(0))?;
+
+        let (head, tail) = sample_ranges(len, sample_len);
+
+        let mut hasher = sampled_hasher(len);
+
+        let mut buffer = [0u8; 8192];
+
+        for (offset, length) in [(0, head), (len - tail, tail)] {
+            reader.seek(SeekFrom::Start(offset))?;
+
+            let mut remaining = length;
+
+            while remaining > 0 {
+                let n = min(remaining, buffer.len() as u64) as usize;
+
+                reader.read_exact(&mut buffer[..n])?;
+                hasher.write(&buffer[..n]);
+
+                count!("entity_tag_hashed_bytes_total", n);
+
+                remaining -= n as u64;
+            }
+        }
+
+        Ok(Self::from_hash(true, salt(hasher.finish())))
+    }
+}
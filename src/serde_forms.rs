@@ -0,0 +1,221 @@
+//! Alternative serde representations of entity tags for `#[serde(with = "...")]`. An `EntityTag` is serialized as a string, such as `W/"xyzzy"`, by default.
+
+use alloc::string::String;
+use core::fmt::{self, Formatter};
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserializer, Serializer,
+};
+
+use crate::{serde_impl::EntityTagVisitor, EntityTag};
+
+const FIELDS: &[&str] = &["weak", "tag"];
+
+struct StructureVisitor;
+
+impl<'de> Visitor<'de> for StructureVisitor {
+    type Value = EntityTag<'static>;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("an entity tag with the fields `weak` and `tag`")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let weak: bool = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let tag: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        EntityTag::with_string(weak, tag).map_err(de::Error::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut weak = None;
+        let mut tag = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "weak" if weak.is_some() => return Err(de::Error::duplicate_field("weak")),
+                "tag" if tag.is_some() => return Err(de::Error::duplicate_field("tag")),
+                "weak" => weak = Some(map.next_value()?),
+                "tag" => tag = Some(map.next_value::<String>()?),
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        let weak = weak.ok_or_else(|| de::Error::missing_field("weak"))?;
+        let tag = tag.ok_or_else(|| de::Error::missing_field("tag"))?;
+
+        EntityTag::with_string(weak, tag).map_err(de::Error::custom)
+    }
+}
+
+/// Serialize an entity tag as a struct, such as `{"weak": true, "tag": "xyzzy"}`. The opaque tag does not include the double quotes.
+pub mod structure {
+    use super::*;
+
+    /// Serialize an entity tag as a struct.
+    pub fn serialize<S: Serializer>(etag: &EntityTag, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("EntityTag", 2)?;
+
+        state.serialize_field("weak", &etag.weak)?;
+        state.serialize_field("tag", etag.get_tag_cow().as_ref())?;
+
+        state.end()
+    }
+
+    /// Deserialize an entity tag from a struct.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<EntityTag<'static>, D::Error> {
+        deserializer.deserialize_struct("EntityTag", FIELDS, StructureVisitor)
+    }
+}
+
+struct StructureOptionVisitor;
+
+impl<'de> Visitor<'de> for StructureOptionVisitor {
+    type Value = Option<EntityTag<'static>>;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("null or an entity tag with the fields `weak` and `tag`")
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        structure::deserialize(deserializer).map(Some)
+    }
+}
+
+struct NullableVisitor;
+
+impl<'de> Visitor<'de> for NullableVisitor {
+    type Value = Option<EntityTag<'static>>;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("null, an empty string or an entity tag such as W/\"xyzzy\"")
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            EntityTagVisitor.visit_str(v).map(Some)
+        }
+    }
+}
+
+/// Serialize an optional entity tag as a struct like `structure` does, or as null.
+pub mod structure_option {
+    use super::*;
+
+    /// Serialize an optional entity tag as a struct or null.
+    #[inline]
+    pub fn serialize<S: Serializer>(
+        etag: &Option<EntityTag>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match etag {
+            Some(etag) => serializer.serialize_some(&Structure(etag)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional entity tag from a struct or null.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<EntityTag<'static>>, D::Error> {
+        deserializer.deserialize_option(StructureOptionVisitor)
+    }
+
+    struct Structure<'a, 't>(&'a EntityTag<'t>);
+
+    impl<'a, 't> serde::Serialize for Structure<'a, 't> {
+        #[inline]
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::structure::serialize(self.0, serializer)
+        }
+    }
+}
+
+/// Serialize an optional entity tag as a string or null. An empty string is deserialized as `None` as well, for APIs which send it when there is no entity tag.
+pub mod nullable {
+    use super::*;
+
+    /// Serialize an optional entity tag as a string or null.
+    #[inline]
+    pub fn serialize<S: Serializer>(
+        etag: &Option<EntityTag>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match etag {
+            Some(etag) => serializer.serialize_some(etag),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional entity tag from a string or null. An empty string is `None`.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<EntityTag<'static>>, D::Error> {
+        deserializer.deserialize_option(NullableVisitor)
+    }
+}
+
+/// The struct form of `structure` as a type for the `serde_as` attribute of the `serde_with` crate, which can be nested, such as `Option<Structure>` or `Vec<Structure>`.
+#[cfg(feature = "serde_with")]
+#[derive(Debug, Clone, Copy)]
+pub struct Structure;
+
+#[cfg(feature = "serde_with")]
+impl<'t> serde_with::SerializeAs<EntityTag<'t>> for Structure {
+    #[inline]
+    fn serialize_as<S: Serializer>(
+        source: &EntityTag<'t>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        structure::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl<'de> serde_with::DeserializeAs<'de, EntityTag<'static>> for Structure {
+    #[inline]
+    fn deserialize_as<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<EntityTag<'static>, D::Error> {
+        structure::deserialize(deserializer)
+    }
+}
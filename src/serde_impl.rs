@@ -0,0 +1,46 @@
+use alloc::string::String;
+use core::fmt::{self, Formatter};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::EntityTag;
+
+impl<'t> Serialize for EntityTag<'t> {
+    /// Serialize this entity tag as a string, such as `W/"xyzzy"`.
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+pub(crate) struct EntityTagVisitor;
+
+impl<'de> Visitor<'de> for EntityTagVisitor {
+    type Value = EntityTag<'static>;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("an entity tag such as W/\"xyzzy\"")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        EntityTag::from_string(v).map_err(E::custom)
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        EntityTag::from_string(v).map_err(E::custom)
+    }
+}
+
+impl<'de, 't> Deserialize<'de> for EntityTag<'t> {
+    /// Deserialize an entity tag from a string. Like `Cow<str>`, the opaque tag is always owned, so that an `EntityTag<'static>` can be deserialized from any input.
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(EntityTagVisitor)
+    }
+}
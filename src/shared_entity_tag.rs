@@ -0,0 +1,115 @@
+use alloc::{borrow::Cow, sync::Arc};
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{buffer, EntityTag, EntityTagError, EntityTagRef};
+
+/// An entity tag whose opaque tag is stored in an `Arc<str>`, so that cloning it into many concurrent tasks, such as the handlers of responses, only increments a reference count.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SharedEntityTag {
+    /// Whether to have a weakness indicator.
+    pub weak: bool,
+    /// *etagc
+    tag:      Arc<str>,
+}
+
+impl SharedEntityTag {
+    /// Construct a new SharedEntityTag. The double quotes of `tag` are optional.
+    #[inline]
+    pub fn with_str<S: ?Sized + AsRef<str>>(weak: bool, tag: &S) -> Result<Self, EntityTagError> {
+        Ok(EntityTagRef::with_str(weak, tag)?.into())
+    }
+
+    /// Get the tag. The double quotes are not included.
+    #[inline]
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Borrow this entity tag as an `EntityTag` without copying the opaque tag.
+    #[inline]
+    pub fn as_entity_tag(&self) -> EntityTag<'_> {
+        EntityTag {
+            weak: self.weak, tag: Cow::Borrowed(&self.tag)
+        }
+    }
+
+    /// Borrow this entity tag as an `EntityTagRef`.
+    #[inline]
+    pub fn as_entity_tag_ref(&self) -> EntityTagRef<'_> {
+        EntityTagRef {
+            weak: self.weak, tag: &self.tag
+        }
+    }
+
+    /// For strong comparison two entity-tags are equivalent if both are not weak and their opaque-tags match character-by-character.
+    #[inline]
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        self.as_entity_tag().strong_eq(other)
+    }
+
+    /// For weak comparison two entity-tags are equivalent if their opaque-tags match character-by-character, regardless of either or both being tagged as "weak".
+    #[inline]
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.as_entity_tag().weak_eq(other)
+    }
+}
+
+impl<'t> From<EntityTagRef<'t>> for SharedEntityTag {
+    #[inline]
+    fn from(etag: EntityTagRef<'t>) -> Self {
+        SharedEntityTag {
+            weak: etag.weak, tag: Arc::from(etag.tag)
+        }
+    }
+}
+
+impl<'t> From<EntityTag<'t>> for SharedEntityTag {
+    #[inline]
+    fn from(etag: EntityTag<'t>) -> Self {
+        let weak = etag.weak;
+
+        let tag = match etag.into_tag() {
+            Cow::Borrowed(tag) => Arc::from(tag),
+            Cow::Owned(tag) => Arc::from(tag),
+        };
+
+        SharedEntityTag {
+            weak,
+            tag,
+        }
+    }
+}
+
+impl<'a, 't> From<&'a EntityTag<'t>> for SharedEntityTag {
+    #[inline]
+    fn from(etag: &'a EntityTag<'t>) -> Self {
+        etag.as_entity_tag_ref().into()
+    }
+}
+
+impl From<SharedEntityTag> for EntityTag<'static> {
+    #[inline]
+    fn from(etag: SharedEntityTag) -> Self {
+        etag.as_entity_tag().into_owned()
+    }
+}
+
+impl FromStr for SharedEntityTag {
+    type Err = EntityTagError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(EntityTagRef::from_str(s)?.into())
+    }
+}
+
+impl Display for SharedEntityTag {
+    /// Format this entity tag like `EntityTag` does. `{:#}` escapes the opaque tag.
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        buffer::fmt_entity_tag(self.weak, &self.tag, f)
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File, Metadata},
+    io,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::{epoch, EntityTag, EntityTagWriter};
+
+/// Describe the length and the modified time of a file and the epoch an entity tag was generated in, which are used to check whether a cached entity tag is stale.
+pub(crate) fn snapshot(metadata: &Metadata, epoch: u32) -> String {
+    let modified = match metadata.modified() {
+        Ok(modified) => match modified.duration_since(UNIX_EPOCH) {
+            Ok(time) => time.as_nanos().to_string(),
+            Err(error) => format!("-{}", error.duration().as_nanos()),
+        },
+        Err(_) => String::from("?"),
+    };
+
+    format!("{} {} {}", metadata.len(), modified, epoch)
+}
+
+/// Encode an entity tag with the snapshot of the metadata it was computed from and the current epoch, in the format `<length> <modified time> <epoch> <entity-tag>`.
+pub(crate) fn encode_record(etag: &EntityTag, metadata: &Metadata) -> String {
+    format!("{} {}", snapshot(metadata, epoch()), etag)
+}
+
+/// Decode a record encoded by `encode_record`. Returns `None` if the record is malformed or does not match the current metadata and the current epoch.
+pub(crate) fn decode_record(record: &str, metadata: &Metadata) -> Option<EntityTag<'static>> {
+    let mut parts = record.trim_end().splitn(4, ' ');
+
+    let (len, modified, epoch_then, etag) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+
+    if format!("{} {} {}", len, modified, epoch_then) != snapshot(metadata, epoch()) {
+        return None;
+    }
+
+    EntityTag::from_string(etag).ok()
+}
+
+/// Hash the content of a file. The metadata is taken before hashing.
+pub(crate) fn hash_file(path: &Path) -> io::Result<(EntityTag<'static>, Metadata)> {
+    let mut file = File::open(path)?;
+
+    let metadata = file.metadata()?;
+
+    let mut writer = EntityTagWriter::new(io::sink());
+
+    io::copy(&mut file, &mut writer)?;
+
+    Ok((writer.etag(), metadata))
+}
+
+impl<'t> EntityTag<'t> {
+    /// Get the path of the sidecar file of a file, which is the path with `.etag` appended, such as `foo.jpg.etag` for `foo.jpg`.
+    #[inline]
+    pub fn sidecar_path<P: AsRef<Path>>(path: P) -> PathBuf {
+        let mut sidecar = OsString::from(path.as_ref());
+
+        sidecar.push(".etag");
+
+        PathBuf::from(sidecar)
+    }
+
+    /// Read the entity tag stored in the sidecar file of a file. Returns `Ok(None)` if the sidecar file does not exist, is malformed, or is stale, which means the length or the modified time of the file, or the epoch, has changed since the entity tag was computed.
+    pub fn read_sidecar<P: AsRef<Path>>(path: P) -> io::Result<Option<EntityTag<'static>>> {
+        let path = path.as_ref();
+
+        let content = match fs::read_to_string(Self::sidecar_path(path)) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        let metadata = fs::metadata(path)?;
+
+        Ok(decode_record(&content, &metadata))
+    }
+
+    /// Write this entity tag into the sidecar file of a file, together with the current epoch and the length and the modified time in `metadata`, which should be taken before the entity tag was computed, so that a file modified during the computation makes the sidecar file stale.
+    pub fn write_sidecar<P: AsRef<Path>>(&self, path: P, metadata: &Metadata) -> io::Result<()> {
+        fs::write(Self::sidecar_path(path), encode_record(self, metadata) + "\n")
+    }
+
+    /// Get the strong entity tag of the content of a file, as `from_data` computes. The entity tag stored in the sidecar file is used if it is current, so the file does not need to be hashed. Otherwise, the file is hashed and the sidecar file is rewritten.
+    pub fn from_path_with_sidecar<P: AsRef<Path>>(path: P) -> io::Result<EntityTag<'static>> {
+        let path = path.as_ref();
+
+        if let Some(etag) = Self::read_sidecar(path)? {
+            if !etag.weak {
+                count!("entity_tag_cache_hits_total", 1, "cache" => "sidecar");
+
+                return Ok(etag);
+            }
+        }
+
+        let (etag, metadata) = hash_file(path)?;
+
+        etag.write_sidecar(path, &metadata)?;
+
+        debug!(path = %path.display(), %etag, "rewrote the sidecar file");
+
+        Ok(etag)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{tag_storage::decode_value, EntityTag, TagStorage};
+
+impl TagStorage for sled::Tree {
+    type Error = sled::Error;
+
+    #[inline]
+    fn get_tag(&mut self, key: &str) -> Result<Option<EntityTag<'static>>, Self::Error> {
+        Ok(self.get(key)?.and_then(|value| decode_value(key, &value)))
+    }
+
+    #[inline]
+    fn set_tag(&mut self, key: &str, etag: &EntityTag) -> Result<(), Self::Error> {
+        self.insert(key, etag.to_bytes()).map(|_| ())
+    }
+
+    #[inline]
+    fn remove_tag(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.remove(key).map(|_| ())
+    }
+}
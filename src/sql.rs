@@ -0,0 +1,53 @@
+use alloc::string::{String, ToString};
+
+use sqlx_core::{
+    database::Database,
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    types::Type,
+};
+
+use crate::EntityTag;
+
+impl<'t, DB: Database> Type<DB> for EntityTag<'t>
+where
+    String: Type<DB>,
+{
+    #[inline]
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    #[inline]
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, 't, DB: Database> Encode<'q, DB> for EntityTag<'t>
+where
+    String: Encode<'q, DB>,
+{
+    /// Encode this entity tag as a text value, such as `W/"xyzzy"`.
+    #[inline]
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, DB>>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r, 't, DB: Database> Decode<'r, DB> for EntityTag<'t>
+where
+    String: Decode<'r, DB>,
+{
+    /// Decode and validate an entity tag from a text value, such as `W/"xyzzy"`.
+    #[inline]
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<'r, DB>>::decode(value)?;
+
+        Ok(EntityTag::from_string(s)?)
+    }
+}
@@ -0,0 +1,23 @@
+use alloc::string::ToString;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::EntityTag;
+
+impl<'t> ToSql for EntityTag<'t> {
+    /// Store this entity tag as a text value, such as `W/"xyzzy"`.
+    #[inline]
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl<'t> FromSql for EntityTag<'t> {
+    /// Load and validate an entity tag from a text value, such as `W/"xyzzy"`.
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        EntityTag::from_str(value.as_str()?)
+            .map(EntityTag::into_owned)
+            .map_err(|error| FromSqlError::Other(error.to_string().into()))
+    }
+}
@@ -0,0 +1,44 @@
+//! [proptest](https://docs.rs/proptest) strategies generating values which follow the grammar of entity tags.
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::{EntityTag, EntityTagList};
+
+/// Generate characters allowed in opaque tags (`etagc`), mostly visible ASCII characters and sometimes obs-text.
+pub fn etagc() -> impl Strategy<Value = char> {
+    prop_oneof![
+        7 => (0x21u8..=0x7E).prop_map(|c| if c == b'"' { '!' } else { c as char }),
+        1 => (0x80u32..=0x10FFFF).prop_filter_map("not a char", char::from_u32),
+    ]
+}
+
+/// Generate opaque tags, which are the part inside the double quotes, including the empty one.
+pub fn opaque_tag() -> impl Strategy<Value = String> {
+    vec(etagc(), 0..32).prop_map(|chars| chars.into_iter().collect())
+}
+
+/// Generate weak and strong entity tags.
+pub fn entity_tag() -> impl Strategy<Value = EntityTag<'static>> {
+    (any::<bool>(), opaque_tag()).prop_map(|(weak, tag)| EntityTag {
+        weak,
+        tag: Cow::from(tag),
+    })
+}
+
+/// Generate `*` and non-empty lists of entity tags.
+pub fn entity_tag_list() -> impl Strategy<Value = EntityTagList<'static>> {
+    prop_oneof![
+        1 => Just(EntityTagList::Any),
+        7 => vec(entity_tag(), 1..8).prop_map(EntityTagList::Tags),
+    ]
+}
+
+/// Generate values of the `If-Match` or `If-None-Match` header field, including the wildcard `*`.
+pub fn entity_tag_list_field_value() -> impl Strategy<Value = String> {
+    entity_tag_list().prop_map(|list| list.to_string())
+}
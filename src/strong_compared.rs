@@ -0,0 +1,66 @@
+use core::{
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::EntityTag;
+
+/// A strong entity tag whose `Eq` and `Hash` follow the strong comparison, defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3.2), so that it can be used as a key of a map for `If-Range` or `If-Match` decisions.
+///
+/// A weak entity tag never matches under the strong comparison, not even itself, so it cannot be wrapped.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StrongCompared<'t>(EntityTag<'t>);
+
+impl<'t> StrongCompared<'t> {
+    /// Wrap a strong entity tag. Returns `None` if it is weak.
+    #[inline]
+    pub fn new(etag: EntityTag<'t>) -> Option<Self> {
+        if etag.weak {
+            None
+        } else {
+            Some(StrongCompared(etag))
+        }
+    }
+
+    /// Whether `etag` matches this entity tag under the strong comparison.
+    #[inline]
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        self.0.strong_eq(etag)
+    }
+
+    /// Extracts the entity tag.
+    #[inline]
+    pub fn into_inner(self) -> EntityTag<'t> {
+        self.0
+    }
+
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> StrongCompared<'static> {
+        StrongCompared(self.0.into_owned())
+    }
+}
+
+impl<'t> Hash for StrongCompared<'t> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.get_tag_cow().hash(state)
+    }
+}
+
+impl<'t> Deref for StrongCompared<'t> {
+    type Target = EntityTag<'t>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'t> Display for StrongCompared<'t> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::string::String;
+
+use crate::{EntityTag, StructuredFieldError};
+
+impl<'t> EntityTag<'t> {
+    /// Serialize this entity tag as an sf-string item, defined in [RFC8941](https://www.rfc-editor.org/rfc/rfc8941#section-3.3.3), so that it can be embedded in a structured header field. For example, `W/"foo"` becomes `"W/\"foo\""`.
+    ///
+    /// Returns `None` if the opaque tag contains obs-text, which cannot be represented by an sf-string.
+    pub fn to_sf_string(&self) -> Option<String> {
+        let tag = self.get_tag_cow();
+
+        if !tag.is_ascii() {
+            return None;
+        }
+
+        let mut s = String::with_capacity(tag.len() + 8);
+
+        s.push('"');
+
+        if self.weak {
+            s.push_str("W/");
+        }
+
+        s.push_str("\\\"");
+
+        for c in tag.chars() {
+            if c == '\\' {
+                s.push('\\');
+            }
+
+            s.push(c);
+        }
+
+        s.push_str("\\\"\"");
+
+        Some(s)
+    }
+
+    /// Parse an sf-string item, defined in [RFC8941](https://www.rfc-editor.org/rfc/rfc8941#section-3.3.3), which contains an entity tag. Parameters are not supported.
+    pub fn from_sf_string<S: ?Sized + AsRef<str>>(
+        value: &S,
+    ) -> Result<EntityTag<'static>, StructuredFieldError> {
+        let s = value.as_ref().trim_matches(' ');
+
+        let s = s
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .ok_or(StructuredFieldError::InvalidString)?;
+
+        let mut etag = String::with_capacity(s.len());
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(c @ ('"' | '\\')) => etag.push(c),
+                    _ => return Err(StructuredFieldError::InvalidString),
+                },
+                '\x20'..='\x7e' if c != '"' => etag.push(c),
+                _ => return Err(StructuredFieldError::InvalidString),
+            }
+        }
+
+        Ok(EntityTag::from_string(etag)?)
+    }
+}
@@ -0,0 +1,34 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::EntityTagError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Possible errors of converting an `EntityTag` from a structured field value.
+pub enum StructuredFieldError {
+    /// The value is not a valid sf-string.
+    InvalidString,
+    /// The content of the sf-string is not a valid entity tag.
+    InvalidEntityTag(EntityTagError),
+}
+
+impl From<EntityTagError> for StructuredFieldError {
+    #[inline]
+    fn from(error: EntityTagError) -> Self {
+        StructuredFieldError::InvalidEntityTag(error)
+    }
+}
+
+impl Display for StructuredFieldError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            StructuredFieldError::InvalidString => f.write_str("invalid sf-string"),
+            StructuredFieldError::InvalidEntityTag(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for StructuredFieldError {}
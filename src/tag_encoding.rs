@@ -0,0 +1,99 @@
+use alloc::string::String;
+use core::num::NonZeroUsize;
+
+use base64::{
+    engine::{
+        general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD},
+        GeneralPurpose,
+    },
+    Engine,
+};
+
+/// The base64 encoding of generated opaque tags, so that they can match the ones generated by an existing system, such as one using the URL-safe alphabet or padding.
+///
+/// The default is the standard alphabet without padding, which `from_data` and the other generating functions use.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct TagEncoding {
+    pub(crate) url_safe: bool,
+    pub(crate) padding:  bool,
+    pub(crate) truncate: Option<NonZeroUsize>,
+}
+
+impl TagEncoding {
+    /// Create the default encoding, the standard alphabet without padding.
+    #[inline]
+    pub const fn new() -> Self {
+        TagEncoding {
+            url_safe: false, padding: false, truncate: None
+        }
+    }
+
+    /// Whether to use the URL-safe alphabet, which has `-` and `_` instead of `+` and `/`.
+    #[inline]
+    pub const fn url_safe(mut self, url_safe: bool) -> Self {
+        self.url_safe = url_safe;
+
+        self
+    }
+
+    /// Whether to pad the opaque tag with `=`.
+    #[inline]
+    pub const fn padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+
+        self
+    }
+
+    /// Truncate the opaque tag to at most `len` characters, for environments where the size of header fields matters, such as long `If-None-Match` lists.
+    ///
+    /// Each character carries 6 bits of the 64-bit hash value, so a change of the representation keeps the same entity tag, and a client is wrongly told that its cached copy is still valid, with a probability of about 1 in 2^(6 × `len`), e.g. 1 in 2^36 (about 69 billion) for 6 characters and 1 in 2^48 for 8 characters. Only the versions of the same resource compete with each other. The hash value cannot be recovered from a truncated opaque tag, so `as_hash_with_encoding` returns `None` for it.
+    ///
+    /// `len` cannot be `0`, because empty opaque tags would make every entity tag the same.
+    #[inline]
+    pub const fn truncate(mut self, len: NonZeroUsize) -> Self {
+        self.truncate = Some(len);
+
+        self
+    }
+
+    #[inline]
+    fn engine(self) -> &'static GeneralPurpose {
+        match (self.url_safe, self.padding) {
+            (false, false) => &STANDARD_NO_PAD,
+            (false, true) => &STANDARD,
+            (true, false) => &URL_SAFE_NO_PAD,
+            (true, true) => &URL_SAFE,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn encode(self, bytes: &[u8]) -> String {
+        let mut tag = self.engine().encode(bytes);
+
+        if let Some(len) = self.truncate {
+            // base64 characters are all ASCII
+            tag.truncate(len.get());
+        }
+
+        tag
+    }
+
+    /// Decode an opaque tag encoding a hash value.
+    pub(crate) fn decode_hash(self, tag: &str) -> Option<u64> {
+        if tag.len() != if self.padding { 12 } else { 11 } {
+            return None;
+        }
+
+        let mut bytes = [0u8; 9];
+
+        match self.engine().decode_slice(tag.as_bytes(), &mut bytes) {
+            Ok(8) => {
+                let mut hash = [0u8; 8];
+                hash.copy_from_slice(&bytes[..8]);
+
+                Some(u64::from_le_bytes(hash))
+            },
+            _ => None,
+        }
+    }
+}
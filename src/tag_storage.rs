@@ -0,0 +1,56 @@
+use core::convert::Infallible;
+
+use crate::{EntityTag, EntityTagStore};
+
+/// A storage of the current entity tags of resources, such as an `EntityTagStore` in memory or a key-value database on disk. The keys are e.g. the URLs or the file paths of the resources.
+///
+/// With the `sled` or `rocksdb` feature, it is implemented for `sled::Tree` and `rocksdb::DBWithThreadMode`, which store the entity tags in the form of `EntityTag::to_bytes`, so that catalogs too large for memory can be served.
+pub trait TagStorage {
+    /// The error type of the storage.
+    type Error;
+
+    /// Get the entity tag stored with the key. Returns `Ok(None)` if there is no such entity tag, e.g. it has been evicted.
+    fn get_tag(&mut self, key: &str) -> Result<Option<EntityTag<'static>>, Self::Error>;
+
+    /// Store an entity tag with the key, replacing the existing one.
+    fn set_tag(&mut self, key: &str, etag: &EntityTag) -> Result<(), Self::Error>;
+
+    /// Remove the entity tag stored with the key, if any.
+    fn remove_tag(&mut self, key: &str) -> Result<(), Self::Error>;
+}
+
+impl TagStorage for EntityTagStore {
+    type Error = Infallible;
+
+    #[inline]
+    fn get_tag(&mut self, key: &str) -> Result<Option<EntityTag<'static>>, Self::Error> {
+        Ok(self.get(key).cloned())
+    }
+
+    #[inline]
+    fn set_tag(&mut self, key: &str, etag: &EntityTag) -> Result<(), Self::Error> {
+        self.insert(key, etag.clone());
+
+        Ok(())
+    }
+
+    #[inline]
+    fn remove_tag(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.remove(key);
+
+        Ok(())
+    }
+}
+
+/// Decode an entity tag stored in a database. A malformed value is treated as absent, like a stale sidecar file.
+#[cfg(any(feature = "sled", feature = "rocksdb", feature = "redis"))]
+pub(crate) fn decode_value(_key: &str, value: &[u8]) -> Option<EntityTag<'static>> {
+    match EntityTag::from_bytes_encoded(value) {
+        Ok((etag, _)) => Some(etag.into_owned()),
+        Err(_error) => {
+            debug!(key = %_key, error = %_error, "ignored a malformed stored entity tag");
+
+            None
+        },
+    }
+}
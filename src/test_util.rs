@@ -0,0 +1,130 @@
+//! Fixtures and assertion helpers for testing caching behavior.
+
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::EntityTag;
+
+/// Assert that two entity tags match under the strong comparison.
+#[macro_export]
+macro_rules! assert_strong_match {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !left.strong_eq(right) {
+                    panic!("assertion failed: `{}` and `{}` do not strongly match", left, right);
+                }
+            },
+        }
+    };
+}
+
+/// Assert that two entity tags match under the weak comparison.
+#[macro_export]
+macro_rules! assert_weak_match {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !left.weak_eq(right) {
+                    panic!("assertion failed: `{}` and `{}` do not weakly match", left, right);
+                }
+            },
+        }
+    };
+}
+
+/// Assert that two entity tags do not match even under the weak comparison.
+#[macro_export]
+macro_rules! assert_no_match {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left.weak_eq(right) {
+                    panic!("assertion failed: `{}` and `{}` match", left, right);
+                }
+            },
+        }
+    };
+}
+
+/// The strong entity tag `""`, whose opaque tag is empty.
+#[inline]
+pub fn empty() -> EntityTag<'static> {
+    EntityTag {
+        weak: false, tag: Cow::Borrowed("")
+    }
+}
+
+/// A strong entity tag whose opaque tag contains obs-text (non-ASCII characters).
+#[inline]
+pub fn obs_text() -> EntityTag<'static> {
+    EntityTag {
+        weak: false, tag: Cow::Borrowed("caf\u{e9}-\u{6f22}\u{5b57}")
+    }
+}
+
+/// A strong entity tag whose opaque tag is 998 bytes long, which makes the `ETag` field line exceed the 998-character line limit of [RFC5322](https://www.rfc-editor.org/rfc/rfc5322#section-2.1.1).
+#[inline]
+pub fn long() -> EntityTag<'static> {
+    let tag: String =
+        (b'!'..=b'~').filter(|&c| c != b'"').map(char::from).cycle().take(998).collect();
+
+    EntityTag {
+        weak: false, tag: Cow::from(tag)
+    }
+}
+
+/// All the canned edge-case entity tags, in both weak and strong forms.
+pub fn edge_cases() -> Vec<EntityTag<'static>> {
+    let mut etags = vec![empty(), obs_text(), long()];
+
+    for i in 0..etags.len() {
+        let mut etag = etags[i].clone();
+
+        etag.weak = true;
+
+        etags.push(etag);
+    }
+
+    etags
+}
+
+#[cfg(feature = "std")]
+/// A builder of the file metadata which `EntityTag::from_file_meta` uses, so that entity tags of files can be predicted without touching the file system.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct FakeMetadata {
+    len:      u64,
+    modified: Option<SystemTime>,
+}
+
+#[cfg(feature = "std")]
+impl FakeMetadata {
+    /// Create an empty file without the modified time.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the length of the file.
+    #[inline]
+    pub fn len(mut self, len: u64) -> Self {
+        self.len = len;
+
+        self
+    }
+
+    /// Set the modified time of the file.
+    #[inline]
+    pub fn modified(mut self, modified: SystemTime) -> Self {
+        self.modified = Some(modified);
+
+        self
+    }
+
+    /// Construct the weak EntityTag that `EntityTag::from_file_meta` returns for a file with this metadata.
+    #[inline]
+    pub fn to_entity_tag(&self) -> EntityTag<'static> {
+        EntityTag::from_file_parts(self.len, self.modified)
+    }
+}
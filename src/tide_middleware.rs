@@ -0,0 +1,131 @@
+//! A middleware and request extensions for the `tide` crate.
+
+use tide::{
+    http::headers::{HeaderValues, IF_NONE_MATCH},
+    Request,
+};
+#[cfg(feature = "generate")]
+use tide::{
+    http::{headers::ETAG, Method},
+    utils::async_trait,
+    Body, Middleware, Next, Response, StatusCode,
+};
+
+#[cfg(feature = "generate")]
+use crate::EntityTag;
+use crate::{EntityTagError, EntityTagList};
+
+/// The parsed `If-None-Match` header field, stored in the request extensions by `ETagMiddleware`.
+struct IfNoneMatch(EntityTagList<'static>);
+
+fn get_list(values: Option<&HeaderValues>) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    match values {
+        Some(values) => EntityTagList::join_values(values.iter().map(|value| Ok(value.as_str()))),
+        None => Ok(None),
+    }
+}
+
+/// Find the `If-None-Match` header fields of a request and parse them into an `EntityTagList`. Multiple occurrences are joined together. Returns `Ok(None)` if the header field is absent.
+#[inline]
+pub fn get_if_none_match<State>(
+    request: &Request<State>,
+) -> Result<Option<EntityTagList<'_>>, EntityTagError> {
+    get_list(request.header(IF_NONE_MATCH))
+}
+
+/// Accessors of the request extensions set by `ETagMiddleware`.
+pub trait RequestExt {
+    /// Get the `If-None-Match` header field parsed by `ETagMiddleware`. Returns `None` if the header field is absent or malformed, or the middleware is not used.
+    fn if_none_match(&self) -> Option<&EntityTagList<'static>>;
+}
+
+impl<State> RequestExt for Request<State> {
+    #[inline]
+    fn if_none_match(&self) -> Option<&EntityTagList<'static>> {
+        self.ext::<IfNoneMatch>().map(|if_none_match| &if_none_match.0)
+    }
+}
+
+/// A middleware which sets the `ETag` header field of successful responses and answers `304 Not Modified` to `GET` and `HEAD` requests whose `If-None-Match` precondition fails.
+///
+/// If the endpoint does not set the `ETag` header field, the body is buffered and hashed with `EntityTag::from_data`. Requests with other methods are never answered with `304 Not Modified`, because the endpoint has already run.
+#[cfg(feature = "generate")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ETagMiddleware {
+    _private: (),
+}
+
+#[cfg(feature = "generate")]
+impl ETagMiddleware {
+    /// Create a new ETagMiddleware.
+    #[inline]
+    pub const fn new() -> Self {
+        ETagMiddleware {
+            _private: ()
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+#[async_trait]
+impl<State: Clone + Send + Sync + 'static> Middleware<State> for ETagMiddleware {
+    async fn handle(&self, mut request: Request<State>, next: Next<'_, State>) -> tide::Result {
+        let if_none_match = match get_if_none_match(&request) {
+            Ok(Some(list)) => Some(list.into_owned()),
+            Ok(None) => None,
+            Err(_error) => {
+                debug!(error = %_error, "ignored a malformed If-None-Match header field");
+
+                None
+            },
+        };
+
+        let method = request.method();
+
+        if let Some(list) = if_none_match.clone() {
+            request.set_ext(IfNoneMatch(list));
+        }
+
+        let mut response: Response = next.run(request).await;
+
+        if response.status() != StatusCode::Ok {
+            return Ok(response);
+        }
+
+        let current = response.header(ETAG).map(|values| EntityTag::from_string(values.as_str()));
+
+        let etag = match current {
+            Some(Ok(etag)) => etag,
+            // leave a malformed entity tag set by the endpoint alone
+            Some(Err(_)) => return Ok(response),
+            None => {
+                let body = response.take_body();
+                let mime = body.mime().clone();
+                let data = body.into_bytes().await?;
+
+                let etag = EntityTag::from_data(&data);
+
+                let mut body = Body::from_bytes(data);
+                body.set_mime(mime);
+                response.set_body(body);
+
+                response.insert_header(ETAG, etag.to_string());
+
+                etag
+            },
+        };
+
+        if let Some(list) = if_none_match {
+            if matches!(method, Method::Get | Method::Head)
+                && list.check_if_none_match(Some(&etag)).is_err()
+            {
+                count!("entity_tag_not_modified_total", 1);
+
+                response.set_status(StatusCode::NotModified);
+                response.set_body(Body::empty());
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,110 @@
+use alloc::{string::ToString, vec::Vec};
+use core::convert::TryFrom;
+
+use headers::{ETag, Header, HeaderValue, IfMatch, IfNoneMatch};
+
+use crate::{http_header::to_header_value, EntityTag, EntityTagError, EntityTagList};
+
+/// Decode a typed header from a header value. It fails if the value is not valid for the header.
+fn decode<H: Header>(value: &HeaderValue) -> Result<H, EntityTagError> {
+    H::decode(&mut core::iter::once(value)).map_err(|_| EntityTagError::InvalidTag)
+}
+
+/// Encode a typed header into a `str`.
+fn encode<H: Header>(header: &H) -> Result<alloc::string::String, EntityTagError> {
+    let mut values: Vec<HeaderValue> = Vec::with_capacity(1);
+
+    header.encode(&mut values);
+
+    let value = values.first().ok_or(EntityTagError::InvalidTag)?;
+
+    // `obs-text` is not visible ASCII, so `HeaderValue::to_str` cannot be used
+    core::str::from_utf8(value.as_bytes()).map(|s| s.into()).map_err(|_| EntityTagError::InvalidTag)
+}
+
+impl<'a, 't> TryFrom<&'a EntityTag<'t>> for ETag {
+    type Error = EntityTagError;
+
+    /// Convert an `EntityTag`. It fails if the entity tag is made by an unchecked constructor with octets which are not allowed in a header field.
+    #[inline]
+    fn try_from(etag: &'a EntityTag<'t>) -> Result<Self, Self::Error> {
+        decode(&to_header_value(etag.to_string().as_bytes())?)
+    }
+}
+
+impl<'t> TryFrom<EntityTag<'t>> for ETag {
+    type Error = EntityTagError;
+
+    /// Convert an `EntityTag`. It fails if the entity tag is made by an unchecked constructor with octets which are not allowed in a header field.
+    #[inline]
+    fn try_from(etag: EntityTag<'t>) -> Result<Self, Self::Error> {
+        ETag::try_from(&etag)
+    }
+}
+
+impl<'a> TryFrom<&'a ETag> for EntityTag<'static> {
+    type Error = EntityTagError;
+
+    /// Convert a `headers::ETag`. It fails if the opaque tag is not valid UTF-8.
+    #[inline]
+    fn try_from(etag: &'a ETag) -> Result<Self, Self::Error> {
+        EntityTag::from_string(encode(etag)?)
+    }
+}
+
+impl TryFrom<ETag> for EntityTag<'static> {
+    type Error = EntityTagError;
+
+    /// Convert a `headers::ETag`. It fails if the opaque tag is not valid UTF-8.
+    #[inline]
+    fn try_from(etag: ETag) -> Result<Self, Self::Error> {
+        EntityTag::try_from(&etag)
+    }
+}
+
+macro_rules! impl_list_conversions {
+    ($header:ty) => {
+        impl<'a, 't> TryFrom<&'a EntityTagList<'t>> for $header {
+            type Error = EntityTagError;
+
+            /// Convert an `EntityTagList`. It fails if the list is empty or has an entity tag made by an unchecked constructor with octets which are not allowed in a header field.
+            #[inline]
+            fn try_from(list: &'a EntityTagList<'t>) -> Result<Self, Self::Error> {
+                decode(&HeaderValue::try_from(list)?)
+            }
+        }
+
+        impl<'t> TryFrom<EntityTagList<'t>> for $header {
+            type Error = EntityTagError;
+
+            /// Convert an `EntityTagList`. It fails if the list is empty or has an entity tag made by an unchecked constructor with octets which are not allowed in a header field.
+            #[inline]
+            fn try_from(list: EntityTagList<'t>) -> Result<Self, Self::Error> {
+                <$header>::try_from(&list)
+            }
+        }
+
+        impl<'a> TryFrom<&'a $header> for EntityTagList<'static> {
+            type Error = EntityTagError;
+
+            /// Convert the typed header. It fails if an opaque tag is not valid UTF-8.
+            #[inline]
+            fn try_from(header: &'a $header) -> Result<Self, Self::Error> {
+                EntityTagList::from_string(encode(header)?)
+            }
+        }
+
+        impl TryFrom<$header> for EntityTagList<'static> {
+            type Error = EntityTagError;
+
+            /// Convert the typed header. It fails if an opaque tag is not valid UTF-8.
+            #[inline]
+            fn try_from(header: $header) -> Result<Self, Self::Error> {
+                EntityTagList::try_from(&header)
+            }
+        }
+    };
+}
+
+impl_list_conversions!(IfMatch);
+impl_list_conversions!(IfNoneMatch);
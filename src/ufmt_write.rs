@@ -0,0 +1,30 @@
+use ufmt_write::uWrite;
+
+#[cfg(feature = "alloc")]
+use crate::EntityTag;
+use crate::EntityTagRef;
+
+#[cfg(feature = "alloc")]
+impl<'t> EntityTag<'t> {
+    /// Write this entity tag, such as `W/"xyzzy"`, into a `ufmt` writer without going through `core::fmt`.
+    ///
+    /// `ufmt` re-exports the `uWrite` trait of the `ufmt-write` crate, so this works with any `ufmt` writer, e.g. to write an entity tag before or after `uwrite!`.
+    #[inline]
+    pub fn write_to_uwrite<W: uWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error> {
+        self.as_entity_tag_ref().write_to_uwrite(writer)
+    }
+}
+
+impl<'t> EntityTagRef<'t> {
+    /// Write this entity tag, such as `W/"xyzzy"`, into a `ufmt` writer without going through `core::fmt`.
+    #[inline]
+    pub fn write_to_uwrite<W: uWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error> {
+        if self.weak {
+            writer.write_str("W/")?;
+        }
+
+        writer.write_char('"')?;
+        writer.write_str(self.get_tag())?;
+        writer.write_char('"')
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::{borrow::Cow, string::String};
+use core::fmt::{self, Display, Formatter};
+
+use crate::{EntityTag, EntityTagError, EntityTagRef};
+
+/// An opaque tag without double quotes which has passed the validation of this crate, so that an `EntityTag` can be constructed from it with `EntityTag::from_validated` without checking again.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ValidatedTag<'t>(Cow<'t, str>);
+
+impl<'t> ValidatedTag<'t> {
+    /// Validate a tag. The double quotes are optional.
+    #[inline]
+    pub fn new<S: ?Sized + AsRef<str>>(tag: &'t S) -> Result<Self, EntityTagError> {
+        Ok(ValidatedTag(Cow::from(EntityTagRef::unquote_tag(tag.as_ref())?)))
+    }
+
+    /// Validate a tag in a `String`. The double quotes are optional.
+    #[inline]
+    pub fn with_string<S: AsRef<str> + Into<String>>(
+        tag: S,
+    ) -> Result<ValidatedTag<'static>, EntityTagError> {
+        Ok(EntityTag::with_string(false, tag)?.into())
+    }
+
+    /// Get the opaque tag. The double quotes are not included.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    /// Convert into a `ValidatedTag` which owns the opaque tag.
+    #[inline]
+    pub fn into_owned(self) -> ValidatedTag<'static> {
+        ValidatedTag(Cow::from(self.0.into_owned()))
+    }
+}
+
+impl<'t> EntityTag<'t> {
+    /// Construct a new EntityTag from a tag which has been validated, without checking again.
+    #[inline]
+    pub fn from_validated(weak: bool, tag: ValidatedTag<'t>) -> Self {
+        EntityTag {
+            weak,
+            tag: tag.0,
+        }
+    }
+}
+
+impl<'t> From<EntityTag<'t>> for ValidatedTag<'t> {
+    /// Take the opaque tag of an entity tag, dropping the weakness indicator.
+    #[inline]
+    fn from(etag: EntityTag<'t>) -> Self {
+        ValidatedTag(etag.tag)
+    }
+}
+
+impl<'t> From<EntityTagRef<'t>> for ValidatedTag<'t> {
+    /// Take the opaque tag of an entity tag, dropping the weakness indicator.
+    #[inline]
+    fn from(etag: EntityTagRef<'t>) -> Self {
+        ValidatedTag(Cow::from(etag.tag))
+    }
+}
+
+impl<'t> AsRef<str> for ValidatedTag<'t> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'t> Display for ValidatedTag<'t> {
+    /// Format the opaque tag with double quotes.
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "\"{}\"", self.0)
+    }
+}
@@ -0,0 +1,98 @@
+//! [wasm-bindgen](https://docs.rs/wasm-bindgen) bindings, so JavaScript code can share the entity tag semantics of this crate.
+//!
+//! The `EntityTag` class is exported to JavaScript.
+//!
+//! ```js
+//! const etag = EntityTag.parse('W/"xyzzy"');
+//!
+//! etag.weakEq(EntityTag.fromData(new TextEncoder().encode("foo")));
+//! etag.toString();
+//! etag.free();
+//! ```
+
+use alloc::string::{String, ToString};
+
+use wasm_bindgen::prelude::*;
+
+use crate::EntityTag;
+
+/// An owned entity tag for JavaScript.
+#[wasm_bindgen(js_name = EntityTag)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WasmEntityTag {
+    etag: EntityTag<'static>,
+}
+
+#[wasm_bindgen(js_class = EntityTag)]
+impl WasmEntityTag {
+    /// Construct a new entity tag. The double quotes of `tag` are optional.
+    #[wasm_bindgen(constructor)]
+    pub fn new(weak: bool, tag: &str) -> Result<WasmEntityTag, JsError> {
+        match EntityTag::with_str(weak, tag) {
+            Ok(etag) => Ok(etag.into_owned().into()),
+            Err(error) => Err(JsError::new(&error.to_string())),
+        }
+    }
+
+    /// Parse an entity tag, such as `W/"xyzzy"`.
+    pub fn parse(value: &str) -> Result<WasmEntityTag, JsError> {
+        match EntityTag::from_str(value) {
+            Ok(etag) => Ok(etag.into_owned().into()),
+            Err(error) => Err(JsError::new(&error.to_string())),
+        }
+    }
+
+    /// Construct a strong entity tag by hashing data, like `EntityTag::from_data`.
+    #[wasm_bindgen(js_name = fromData)]
+    pub fn from_data(data: &[u8]) -> WasmEntityTag {
+        EntityTag::from_data(data).into()
+    }
+
+    /// Whether the entity tag is weak.
+    #[wasm_bindgen(getter)]
+    pub fn weak(&self) -> bool {
+        self.etag.weak
+    }
+
+    /// The opaque tag without the double quotes.
+    #[wasm_bindgen(getter)]
+    pub fn tag(&self) -> String {
+        String::from(self.etag.get_tag_cow().as_ref())
+    }
+
+    /// The strong comparison.
+    #[wasm_bindgen(js_name = strongEq)]
+    pub fn strong_eq(&self, other: &WasmEntityTag) -> bool {
+        self.etag.strong_eq(&other.etag)
+    }
+
+    /// The weak comparison.
+    #[wasm_bindgen(js_name = weakEq)]
+    pub fn weak_eq(&self, other: &WasmEntityTag) -> bool {
+        self.etag.weak_eq(&other.etag)
+    }
+
+    /// Format the entity tag, such as `W/"xyzzy"`.
+    #[allow(clippy::inherent_to_string)]
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.etag.to_string()
+    }
+}
+
+impl WasmEntityTag {
+    /// Unwrap the entity tag.
+    #[inline]
+    pub fn into_inner(self) -> EntityTag<'static> {
+        self.etag
+    }
+}
+
+impl From<EntityTag<'static>> for WasmEntityTag {
+    #[inline]
+    fn from(etag: EntityTag<'static>) -> Self {
+        WasmEntityTag {
+            etag,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use alloc::vec::Vec;
+
+use super::IfHeaderError;
+use crate::EntityTag;
+
+/// A state token or an entity tag in a list of the `If` header field.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum IfConditionKind<'t> {
+    /// A state token (e.g. a lock token). The angle brackets are not included.
+    StateToken(&'t str),
+    /// An entity tag, enclosed in square brackets in the field value.
+    EntityTag(EntityTag<'t>),
+}
+
+/// A condition in a list of the `If` header field.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IfCondition<'t> {
+    /// Whether the condition is negated by `Not`.
+    pub not:  bool,
+    /// The state token or the entity tag to compare with.
+    pub kind: IfConditionKind<'t>,
+}
+
+/// A parenthesized list of the `If` header field. All of its conditions need to be true for the list to be true.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IfList<'t> {
+    /// The resource tag which the list applies to, or `None` for an untagged list. The angle brackets are not included.
+    pub resource:   Option<&'t str>,
+    /// The conditions, in the order they appear in the field value.
+    pub conditions: Vec<IfCondition<'t>>,
+}
+
+/// The value of the WebDAV `If` header field. The field is true if any of its lists is true.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IfHeader<'t> {
+    /// The lists, in the order they appear in the field value.
+    pub lists: Vec<IfList<'t>>,
+}
+
+struct Parser<'t> {
+    s:   &'t str,
+    pos: usize,
+}
+
+impl<'t> Parser<'t> {
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).copied()
+    }
+
+    #[inline]
+    fn skip_lws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\r' | b'\n') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn expect(&mut self, c: u8) -> Result<(), IfHeaderError> {
+        match self.peek() {
+            Some(e) if e == c => {
+                self.pos += 1;
+
+                Ok(())
+            },
+            Some(_) => Err(IfHeaderError::UnexpectedCharacter(self.pos)),
+            None => Err(IfHeaderError::UnexpectedEnd),
+        }
+    }
+
+    fn coded_url(&mut self) -> Result<&'t str, IfHeaderError> {
+        self.expect(b'<')?;
+
+        let end = match self.s[self.pos..].find('>') {
+            Some(index) => self.pos + index,
+            None => return Err(IfHeaderError::UnexpectedEnd),
+        };
+
+        let url = &self.s[self.pos..end];
+
+        self.pos = end + 1;
+
+        Ok(url)
+    }
+
+    fn condition(&mut self) -> Result<IfCondition<'t>, IfHeaderError> {
+        let not = match self.s.get(self.pos..(self.pos + 3)) {
+            Some(s) if s.eq_ignore_ascii_case("Not") => {
+                self.pos += 3;
+                self.skip_lws();
+
+                true
+            },
+            _ => false,
+        };
+
+        let kind = match self.peek() {
+            Some(b'<') => IfConditionKind::StateToken(self.coded_url()?),
+            Some(b'[') => {
+                self.pos += 1;
+                self.skip_lws();
+
+                let (etag, rest) = EntityTag::parse_prefix(&self.s[self.pos..])?;
+
+                self.pos = self.s.len() - rest.len();
+                self.skip_lws();
+                self.expect(b']')?;
+
+                IfConditionKind::EntityTag(etag)
+            },
+            Some(_) => return Err(IfHeaderError::UnexpectedCharacter(self.pos)),
+            None => return Err(IfHeaderError::UnexpectedEnd),
+        };
+
+        Ok(IfCondition {
+            not,
+            kind,
+        })
+    }
+
+    fn list(&mut self) -> Result<Vec<IfCondition<'t>>, IfHeaderError> {
+        self.expect(b'(')?;
+
+        let mut conditions = Vec::new();
+
+        loop {
+            self.skip_lws();
+
+            match self.peek() {
+                Some(b')') => {
+                    if conditions.is_empty() {
+                        return Err(IfHeaderError::UnexpectedCharacter(self.pos));
+                    }
+
+                    self.pos += 1;
+
+                    return Ok(conditions);
+                },
+                Some(_) => conditions.push(self.condition()?),
+                None => return Err(IfHeaderError::UnexpectedEnd),
+            }
+        }
+    }
+}
+
+impl<'t> IfHeader<'t> {
+    /// Parse the value of the `If` header field.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: ?Sized + AsRef<str>>(value: &'t S) -> Result<Self, IfHeaderError> {
+        let mut parser = Parser {
+            s: value.as_ref(), pos: 0
+        };
+
+        let mut lists = Vec::new();
+        let mut tagged = None;
+        let mut resource = None;
+
+        loop {
+            parser.skip_lws();
+
+            match parser.peek() {
+                Some(b'<') => {
+                    if tagged == Some(false) {
+                        return Err(IfHeaderError::MixedLists);
+                    }
+
+                    tagged = Some(true);
+                    resource = Some(parser.coded_url()?);
+
+                    parser.skip_lws();
+
+                    // a resource tag needs to be followed by at least one list
+                    match parser.peek() {
+                        Some(b'(') => (),
+                        Some(_) => return Err(IfHeaderError::UnexpectedCharacter(parser.pos)),
+                        None => return Err(IfHeaderError::UnexpectedEnd),
+                    }
+                },
+                Some(b'(') => {
+                    if tagged.is_none() {
+                        tagged = Some(false);
+                    }
+
+                    lists.push(IfList {
+                        resource,
+                        conditions: parser.list()?,
+                    });
+                },
+                Some(_) => return Err(IfHeaderError::UnexpectedCharacter(parser.pos)),
+                None => break,
+            }
+        }
+
+        if lists.is_empty() {
+            return Err(IfHeaderError::UnexpectedEnd);
+        }
+
+        Ok(IfHeader {
+            lists,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::EntityTagError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Possible errors of `IfHeader`.
+pub enum IfHeaderError {
+    /// The field value ends unexpectedly.
+    UnexpectedEnd,
+    /// An unexpected character is found at the given byte index.
+    UnexpectedCharacter(usize),
+    /// Tagged lists and untagged lists are mixed.
+    MixedLists,
+    /// An entity tag in a condition is invalid.
+    InvalidEntityTag(EntityTagError),
+}
+
+impl From<EntityTagError> for IfHeaderError {
+    #[inline]
+    fn from(error: EntityTagError) -> Self {
+        IfHeaderError::InvalidEntityTag(error)
+    }
+}
+
+impl Display for IfHeaderError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            IfHeaderError::UnexpectedEnd => f.write_str("the field value ends unexpectedly"),
+            IfHeaderError::UnexpectedCharacter(index) => {
+                f.write_fmt(format_args!("unexpected character at {}", index))
+            },
+            IfHeaderError::MixedLists => f.write_str("tagged and untagged lists are mixed"),
+            IfHeaderError::InvalidEntityTag(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for IfHeaderError {}
@@ -0,0 +1,7 @@
+//! Support for the WebDAV `If` header field, defined in [RFC4918](https://www.rfc-editor.org/rfc/rfc4918#section-10.4).
+
+mod if_header;
+mod if_header_error;
+
+pub use if_header::{IfCondition, IfConditionKind, IfHeader, IfList};
+pub use if_header_error::IfHeaderError;
@@ -0,0 +1,108 @@
+#![cfg(feature = "generate")]
+
+// the epoch is global to the process, so it is tested apart from the other tests, which generate entity tags concurrently, and in a single test
+
+use entity_tag::{bump_epoch, set_epoch, EntityTag, EntityTagHasher, RowVersionTag};
+
+#[test]
+fn epoch() {
+    let before = EntityTag::from_data(b"foo");
+    let unsalted_before = unsalted();
+    let sampled_before = EntityTag::from_data_sampled(b"foo", 1);
+    assert_eq!("\"972Sf7Z4eu8\"", before.to_string());
+    assert_eq!(0, entity_tag::epoch());
+
+    #[cfg(feature = "std")]
+    let path = {
+        let path = std::env::temp_dir().join(format!("entity-tag-epoch-{}", std::process::id()));
+
+        std::fs::write(&path, b"foo").unwrap();
+
+        path
+    };
+
+    #[cfg(feature = "std")]
+    let cache = entity_tag::FileTagCache::new();
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(before, cache.get(&path).unwrap());
+        assert_eq!(before, EntityTag::from_path_with_sidecar(&path).unwrap());
+        assert_eq!(Some(before.clone()), EntityTag::read_sidecar(&path).unwrap());
+    }
+
+    assert_eq!(1, bump_epoch());
+    assert_eq!(1, entity_tag::epoch());
+
+    let after = EntityTag::from_data(b"foo");
+    assert_ne!(before, after);
+    assert_eq!(after, EntityTag::from_data(b"foo"));
+    assert_ne!(sampled_before, EntityTag::from_data_sampled(b"foo", 1));
+
+    let mut hasher = EntityTagHasher::new();
+    hasher.write(b"foo");
+    assert_eq!(after, hasher.finish());
+
+    // the entity tags which are not generated by hashing or must match other software stay the same
+    assert_eq!(unsalted_before, unsalted());
+
+    // the hash value of an entity tag still round-trips
+    assert_eq!(after, EntityTag::from_hash(false, after.as_hash().unwrap()));
+
+    // the cached entity tags of files are stale after the epoch changes
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(None, EntityTag::read_sidecar(&path).unwrap());
+        assert_eq!(after, EntityTag::from_path_with_sidecar(&path).unwrap());
+        assert_eq!(after, cache.get(&path).unwrap());
+
+        std::fs::remove_file(EntityTag::sidecar_path(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    set_epoch(2);
+    assert_ne!(after, EntityTag::from_data(b"foo"));
+
+    // the epoch never wraps around to `0`
+    set_epoch(u32::MAX);
+    assert_eq!(1, bump_epoch());
+
+    set_epoch(0);
+    assert_eq!(before, EntityTag::from_data(b"foo"));
+}
+
+fn unsalted() -> Vec<EntityTag<'static>> {
+    let mut tags = vec![
+        EntityTag::from_hash(false, 1),
+        EntityTag::from_hash_with_encoding(true, 1, entity_tag::TagEncoding::new()),
+        EntityTag::from_components(false, '-', [1024, 1]).unwrap(),
+        EntityTag::from_git_blob_hash("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391").unwrap(),
+        EntityTag::from_digest(b"foo"),
+        EntityTag::from_digest_field(
+            "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:",
+            entity_tag::digest::DigestAlgorithm::Sha256,
+        )
+        .unwrap(),
+        EntityTag::from_sri("sha256-X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=").unwrap(),
+        1u64.to_entity_tag(),
+    ];
+
+    #[cfg(feature = "md-5")]
+    tags.push(EntityTag::from_data_md5(b"foo"));
+
+    #[cfg(feature = "sha1")]
+    tags.push(EntityTag::from_git_blob(b"foo"));
+
+    #[cfg(feature = "sha2")]
+    tags.push(EntityTag::from_rails_cache_key("foo/1", entity_tag::RailsDigest::Sha256));
+
+    #[cfg(feature = "std")]
+    tags.push(
+        EntityTag::from_file_meta_actix_files(
+            &std::fs::metadata(env!("CARGO_MANIFEST_DIR")).unwrap(),
+        )
+        .unwrap(),
+    );
+
+    tags
+}
@@ -0,0 +1,26 @@
+use entity_tag::{EntityTagError, EntityTagRef};
+
+#[test]
+fn parse_and_compare_without_alloc() {
+    let (etag, rest) = EntityTagRef::parse_prefix("W/\"foo\", \"bar\"").unwrap();
+
+    assert!(etag.weak);
+    assert_eq!("foo", etag.get_tag());
+    assert_eq!(", \"bar\"", rest);
+
+    let (strong, rest) = EntityTagRef::parse_prefix(&rest[2..]).unwrap();
+
+    assert!(!strong.weak);
+    assert_eq!("", rest);
+
+    assert!(strong.weak_ne(&etag));
+    assert!(etag.weak_eq(&EntityTagRef::with_str(false, "foo").unwrap()));
+    assert!(etag.strong_ne(&EntityTagRef::with_str(true, "foo").unwrap()));
+
+    assert_eq!(Err(EntityTagError::MissingClosingDoubleQuote), EntityTagRef::parse_prefix("\"foo"));
+
+    let mut buf = [0u8; 7];
+    assert_eq!(Ok(7), etag.write_to_slice(&mut buf));
+    assert_eq!(b"W/\"foo\"", &buf);
+    assert_eq!(5, strong.write_to_slice(&mut buf[..4]).unwrap_err().required());
+}
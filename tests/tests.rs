@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use entity_tag::*;
 
 #[test]
@@ -118,12 +120,13 @@ fn etag_parse_failures() {
     assert_eq!(Err(EntityTagError::MissingClosingDoubleQuote), EntityTag::from_str("\"no-dquote"));
 }
 
+#[cfg(feature = "generate")]
 #[test]
 fn from_data() {
     assert_eq!("\"SE+HLI9tiaw\"", EntityTag::from_data(&[1, 2, 3, 4]).to_string());
 }
 
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "generate"))]
 #[test]
 fn from_file_meta() {
     let file = std::fs::File::open("tests/data/P1060382.JPG").unwrap();
@@ -134,3 +137,2670 @@ fn from_file_meta() {
 
     assert!(etag.weak);
 }
+
+#[cfg(feature = "http")]
+#[test]
+fn header_names() {
+    assert!(EntityTag::HEADER_NAME.eq_ignore_ascii_case(EntityTag::HEADER.as_str()));
+    assert!(
+        EntityTag::IF_MATCH_HEADER_NAME.eq_ignore_ascii_case(EntityTag::IF_MATCH_HEADER.as_str())
+    );
+    assert!(EntityTag::IF_NONE_MATCH_HEADER_NAME
+        .eq_ignore_ascii_case(EntityTag::IF_NONE_MATCH_HEADER.as_str()));
+    assert!(
+        EntityTag::IF_RANGE_HEADER_NAME.eq_ignore_ascii_case(EntityTag::IF_RANGE_HEADER.as_str())
+    );
+}
+
+#[test]
+fn list_parse() {
+    assert_eq!(EntityTagList::Any, EntityTagList::from_str(" * ").unwrap());
+    assert_eq!(
+        EntityTagList::Tags(vec![
+            EntityTag::with_str(false, "a").unwrap(),
+            EntityTag::with_str(true, "b,c").unwrap(),
+        ]),
+        EntityTagList::from_str("\"a\" ,\tW/\"b,c\"").unwrap()
+    );
+    assert_eq!(
+        EntityTagList::from_str("\"a\", \"b\"").unwrap(),
+        EntityTagList::from_string(String::from("\"a\", \"b\"")).unwrap()
+    );
+
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTagList::from_str(""));
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTagList::from_str("\"a\" \"b\""));
+    assert_eq!(
+        Err(EntityTagError::MissingStartingDoubleQuote),
+        EntityTagList::from_str("\"a\", *")
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_get_lists() {
+    use http::{HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+
+    assert_eq!(None, http_header::get_if_none_match(&headers).unwrap());
+
+    headers.append(EntityTag::IF_NONE_MATCH_HEADER, HeaderValue::from_static("\"a\", W/\"b\""));
+    headers.append(EntityTag::IF_NONE_MATCH_HEADER, HeaderValue::from_static("\"c\""));
+    headers.append(EntityTag::IF_MATCH_HEADER, HeaderValue::from_static("*"));
+
+    assert_eq!(
+        Some(EntityTagList::from_str("\"a\", W/\"b\", \"c\"").unwrap()),
+        http_header::get_if_none_match(&headers).unwrap()
+    );
+    assert_eq!(Some(EntityTagList::Any), http_header::get_if_match(&headers).unwrap());
+
+    headers.append(EntityTag::IF_MATCH_HEADER, HeaderValue::from_static("\"a\""));
+
+    assert_eq!(Err(EntityTagError::InvalidTag), http_header::get_if_match(&headers));
+
+    // obs-text
+    let mut headers = HeaderMap::new();
+    headers
+        .append(EntityTag::IF_MATCH_HEADER, HeaderValue::from_bytes("\"é\"".as_bytes()).unwrap());
+
+    assert_eq!(
+        Some(EntityTagList::from_str("\"é\"").unwrap()),
+        http_header::get_if_match(&headers).unwrap()
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_header_names() {
+    assert_eq!(http::header::ETAG, EntityTag::HEADER);
+    assert_eq!(http::header::IF_MATCH, EntityTag::IF_MATCH_HEADER);
+    assert_eq!(http::header::IF_NONE_MATCH, EntityTag::IF_NONE_MATCH_HEADER);
+    assert_eq!(http::header::IF_RANGE, EntityTag::IF_RANGE_HEADER);
+
+    assert!(EntityTag::HEADER_NAME.eq_ignore_ascii_case(EntityTag::HEADER.as_str()));
+
+    assert_eq!(EntityTag::IF_MATCH_HEADER, PreconditionHeader::IfMatch.header_name());
+    assert_eq!(EntityTag::IF_NONE_MATCH_HEADER, PreconditionHeader::IfNoneMatch.header_name());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_not_modified_headers() {
+    use http::{header, HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("max-age=60"));
+    headers.append(header::VARY, HeaderValue::from_static("Accept"));
+    headers.append(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("3"));
+    headers
+        .insert(header::LAST_MODIFIED, HeaderValue::from_static("Tue, 15 Nov 1994 12:45:26 GMT"));
+
+    let etag = EntityTag::with_str(false, "foo").unwrap();
+
+    let not_modified = http_header::not_modified_headers(Some(&etag), &headers);
+
+    assert_eq!("\"foo\"", not_modified.get(header::ETAG).unwrap());
+    assert_eq!("max-age=60", not_modified.get(header::CACHE_CONTROL).unwrap());
+    assert_eq!(2, not_modified.get_all(header::VARY).iter().count());
+    assert!(!not_modified.contains_key(header::CONTENT_TYPE));
+    assert!(!not_modified.contains_key(header::CONTENT_LENGTH));
+    assert!(!not_modified.contains_key(header::LAST_MODIFIED));
+
+    let not_modified = http_header::not_modified_headers(None, &headers);
+
+    assert!(!not_modified.contains_key(header::ETAG));
+    assert!(not_modified.contains_key(header::LAST_MODIFIED));
+}
+
+#[test]
+fn list_preconditions() {
+    let strong = EntityTag::with_str(false, "a").unwrap();
+    let weak = EntityTag::with_str(true, "a").unwrap();
+
+    let list = EntityTagList::from_str("\"a\", \"b\"").unwrap();
+
+    assert!(list.check_if_match(Some(&strong)).is_ok());
+    assert_eq!(
+        PreconditionFailure::NoneMatched,
+        list.check_if_match(Some(&weak)).unwrap_err().failure()
+    );
+    assert_eq!(PreconditionFailure::NoneMatched, list.check_if_match(None).unwrap_err().failure());
+    assert!(EntityTagList::Any.check_if_match(Some(&weak)).is_ok());
+    assert_eq!(
+        PreconditionFailure::NoRepresentation,
+        EntityTagList::Any.check_if_match(None).unwrap_err().failure()
+    );
+
+    let error = list.check_if_none_match(Some(&weak)).unwrap_err();
+    assert_eq!(PreconditionHeader::IfNoneMatch, error.header());
+    assert_eq!(PreconditionFailure::Matched, error.failure());
+    assert!(!error.is_not_modified());
+    assert!(list.check_if_none_match(None).is_ok());
+    assert!(EntityTagList::from_str("\"b\"").unwrap().check_if_none_match(Some(&weak)).is_ok());
+    assert_eq!(
+        PreconditionFailure::RepresentationExists,
+        EntityTagList::Any.check_if_none_match(Some(&strong)).unwrap_err().failure()
+    );
+    assert!(EntityTagList::Any.check_if_none_match(None).is_ok());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_evaluate_preconditions() {
+    use http::{HeaderMap, HeaderValue, Method, StatusCode};
+
+    let current = EntityTag::with_str(false, "a").unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(EntityTag::IF_NONE_MATCH_HEADER, HeaderValue::from_static("W/\"a\""));
+
+    let error =
+        http_header::evaluate_preconditions(&Method::GET, &headers, Some(&current)).unwrap_err();
+    assert!(error.is_not_modified());
+    assert_eq!(StatusCode::NOT_MODIFIED, error.status_code());
+
+    let error =
+        http_header::evaluate_preconditions(&Method::PUT, &headers, Some(&current)).unwrap_err();
+    assert!(!error.is_not_modified());
+    assert_eq!(StatusCode::PRECONDITION_FAILED, error.status_code());
+
+    assert!(http_header::evaluate_preconditions(&Method::PUT, &headers, None).is_ok());
+
+    headers.insert(EntityTag::IF_MATCH_HEADER, HeaderValue::from_static("\"b\""));
+
+    let error =
+        http_header::evaluate_preconditions(&Method::GET, &headers, Some(&current)).unwrap_err();
+    assert_eq!(PreconditionHeader::IfMatch, error.header());
+    assert_eq!(StatusCode::PRECONDITION_FAILED, error.status_code());
+}
+
+#[test]
+fn webdav_if_header() {
+    use entity_tag::webdav::*;
+
+    let header = IfHeader::from_str(
+        "<http://example.com/a> (<urn:uuid:181d4fae> [W/\"x\"]) (Not <DAV:no-lock>)\r\n </b> \
+         ([\"y\"])",
+    )
+    .unwrap();
+
+    assert_eq!(3, header.lists.len());
+    assert_eq!(Some("http://example.com/a"), header.lists[0].resource);
+    assert_eq!(
+        vec![
+            IfCondition {
+                not: false, kind: IfConditionKind::StateToken("urn:uuid:181d4fae")
+            },
+            IfCondition {
+                not:  false,
+                kind: IfConditionKind::EntityTag(EntityTag::with_str(true, "x").unwrap()),
+            },
+        ],
+        header.lists[0].conditions
+    );
+    assert_eq!(Some("http://example.com/a"), header.lists[1].resource);
+    assert!(header.lists[1].conditions[0].not);
+    assert_eq!(Some("/b"), header.lists[2].resource);
+
+    let header = IfHeader::from_str("([\"y\"])").unwrap();
+    assert_eq!(None, header.lists[0].resource);
+
+    assert_eq!(Err(IfHeaderError::MixedLists), IfHeader::from_str("(<a>) </b> (<c>)"));
+    assert_eq!(Err(IfHeaderError::UnexpectedEnd), IfHeader::from_str("(<a>"));
+    assert_eq!(Err(IfHeaderError::UnexpectedCharacter(1)), IfHeader::from_str("()"));
+    assert_eq!(
+        Err(IfHeaderError::InvalidEntityTag(EntityTagError::MissingStartingDoubleQuote)),
+        IfHeader::from_str("([y])")
+    );
+}
+
+#[test]
+fn batch_preconditions() {
+    let a = EntityTag::with_str(false, "a").unwrap();
+    let b = EntityTag::with_str(false, "b").unwrap();
+
+    let if_match_a = Precondition::IfMatch(EntityTagList::from_str("\"a\"").unwrap());
+    let if_none_match_any = Precondition::IfNoneMatch(EntityTagList::Any);
+
+    let outcome =
+        evaluate_batch(vec![("/a", Some(&a), &if_match_a), ("/c", None, &if_none_match_any)]);
+    assert!(outcome.is_satisfied());
+    assert_eq!(0, outcome.failures().count());
+
+    let outcome = evaluate_batch(vec![
+        ("/a", Some(&a), &if_match_a),
+        ("/b", Some(&b), &if_match_a),
+        ("/c", Some(&a), &if_none_match_any),
+    ]);
+    assert!(!outcome.is_satisfied());
+    assert_eq!(
+        vec!["/b", "/c"],
+        outcome.failures().map(|(resource, _)| *resource).collect::<Vec<_>>()
+    );
+    assert_eq!(3, outcome.outcomes().len());
+}
+
+#[test]
+fn structured_field() {
+    assert_eq!(
+        "\"W/\\\"a\\\\b\\\"\"",
+        EntityTag::with_str(true, "a\\b").unwrap().to_sf_string().unwrap()
+    );
+    assert_eq!("\"\\\"\\\"\"", EntityTag::with_str(false, "").unwrap().to_sf_string().unwrap());
+    assert_eq!(None, EntityTag::with_str(false, "é").unwrap().to_sf_string());
+
+    assert_eq!(
+        EntityTag::with_str(true, "a\\b").unwrap(),
+        EntityTag::from_sf_string(" \"W/\\\"a\\\\b\\\"\" ").unwrap()
+    );
+    assert_eq!(Err(StructuredFieldError::InvalidString), EntityTag::from_sf_string("\"\\a\""));
+    assert_eq!(Err(StructuredFieldError::InvalidString), EntityTag::from_sf_string("W/\"a\""));
+    assert_eq!(
+        Err(StructuredFieldError::InvalidEntityTag(EntityTagError::MissingStartingDoubleQuote)),
+        EntityTag::from_sf_string("\"a\"")
+    );
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn digest_fields() {
+    use entity_tag::digest::*;
+
+    const SHA_256: &str = "X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=";
+
+    let field = format!("sha-512=:{}{}:, sha-256=:{}:;p=1", SHA_256, SHA_256, SHA_256);
+
+    let etag = EntityTag::from_digest_field(&field, DigestAlgorithm::Sha256).unwrap();
+    assert!(!etag.weak);
+    assert_eq!(SHA_256, etag.get_tag());
+    assert_eq!(
+        Some(format!("sha-256=:{}:", SHA_256)),
+        etag.to_digest_field(DigestAlgorithm::Sha256)
+    );
+    assert_eq!(None, etag.to_digest_field(DigestAlgorithm::Sha512));
+
+    assert_eq!(
+        Err(DigestError::InvalidDigest),
+        EntityTag::from_digest_field(&field, DigestAlgorithm::Sha512)
+    );
+    assert_eq!(
+        Err(DigestError::MissingAlgorithm),
+        EntityTag::from_digest_field("md5=:AAAA:", DigestAlgorithm::Sha256)
+    );
+    assert_eq!(
+        etag,
+        EntityTag::from_digest_field(
+            &format!("sha-256=:{}:", SHA_256.trim_end_matches('=')),
+            DigestAlgorithm::Sha256
+        )
+        .unwrap()
+    );
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn digest_sri() {
+    use entity_tag::digest::*;
+
+    const SHA_256: &str = "sha256-X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=";
+    const SHA_384: &str = "sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC";
+
+    let etag = EntityTag::from_sri(SHA_256).unwrap();
+    assert!(!etag.weak);
+    assert_eq!(Some(String::from(SHA_256)), etag.to_sri(DigestAlgorithm::Sha256));
+    assert_eq!(None, etag.to_sri(DigestAlgorithm::Sha384));
+
+    let etag = EntityTag::from_sri(&format!("{} {}?foo md5-AAAA", SHA_256, SHA_384)).unwrap();
+    assert_eq!(Some(String::from(SHA_384)), etag.to_sri(DigestAlgorithm::Sha384));
+
+    assert_eq!(Err(DigestError::MissingAlgorithm), EntityTag::from_sri("md5-AAAA"));
+    assert_eq!(Err(DigestError::InvalidDigest), EntityTag::from_sri("sha256-AAAA"));
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn hasher() {
+    let mut hasher = EntityTagHasher::new();
+    hasher.write(&[1, 2]);
+    hasher.write(&[]);
+    hasher.write(&[3, 4]);
+
+    assert_eq!(EntityTag::from_data(&[1, 2, 3, 4]), hasher.finish());
+}
+
+#[cfg(all(feature = "sha2", feature = "generate"))]
+#[test]
+fn hasher_with_digest() {
+    use entity_tag::digest::*;
+
+    let mut hasher = EntityTagHasher::with_digest(DigestAlgorithm::Sha256);
+    hasher.write(b"hello world");
+
+    let (etag, digest) = hasher.finish_with_digest();
+    let (algorithm, digest) = digest.unwrap();
+
+    assert_eq!(EntityTag::from_data(b"hello world"), etag);
+    assert_eq!(DigestAlgorithm::Sha256, algorithm);
+    assert_eq!(
+        "sha-256=:uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=:",
+        format_digest_field(&[(algorithm, &digest)])
+    );
+
+    assert_eq!(
+        (etag, digest),
+        EntityTag::from_data_with_digest(b"hello world", DigestAlgorithm::Sha256)
+    );
+    assert_eq!(None, EntityTagHasher::new().finish_with_digest().1);
+}
+
+#[test]
+fn git_blob() {
+    assert_eq!(
+        "\"3b18e512dba79e4c8300dd08aeb37f8e728b8dad\"",
+        EntityTag::from_git_blob_hash("3B18E512DBA79E4C8300DD08AEB37F8E728B8DAD")
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTag::from_git_blob_hash("3b18e512"));
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        EntityTag::from_git_blob_hash("3b18e512dba79e4c8300dd08aeb37f8e728b8daz")
+    );
+
+    #[cfg(feature = "sha1")]
+    assert_eq!(
+        EntityTag::from_git_blob_hash("3b18e512dba79e4c8300dd08aeb37f8e728b8dad").unwrap(),
+        EntityTag::from_git_blob(b"hello world\n")
+    );
+}
+
+#[cfg(feature = "md-5")]
+#[test]
+fn from_data_md5() {
+    assert_eq!(
+        "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"",
+        EntityTag::from_data_md5(b"hello world").to_string()
+    );
+}
+
+#[cfg(all(feature = "md-5", feature = "sha1", feature = "sha2"))]
+#[test]
+fn from_rails_cache_key() {
+    // the hex digests of the keys themselves, as `ActiveSupport::Digest.hexdigest(expand_cache_key(key))` computes, without any namespace
+    assert_eq!(
+        "W/\"27905018b2bc07310a4a22ce03658d48\"",
+        EntityTag::from_rails_cache_key("posts/1-20240101000000", RailsDigest::Md5).to_string()
+    );
+    assert_eq!(
+        "W/\"fe34d4dce30ee5a4f34e145f5ebdc892\"",
+        EntityTag::from_rails_cache_key("posts/1", RailsDigest::Sha1).to_string()
+    );
+    assert_eq!(
+        "W/\"c526a7dba7def3cc61b40bc8aba14d7a\"",
+        EntityTag::from_rails_cache_key("posts/1", RailsDigest::Sha256).to_string()
+    );
+}
+
+#[test]
+fn from_str_iis() {
+    let etag = EntityTag::with_str(false, "86c41a6a7a9be1").unwrap();
+
+    assert_eq!(etag, EntityTag::from_str_iis("\"86c41a6a7a9be1:0\"").unwrap());
+    assert_eq!(etag, EntityTag::from_str_iis(" 86c41a6a7a9be1:2b4 ").unwrap());
+    assert!(EntityTag::from_str_iis("W/\"86c41a6a7a9be1:0\"").unwrap().weak_eq(&etag));
+    assert_eq!(
+        EntityTag::with_str(false, "foo:bar").unwrap(),
+        EntityTag::from_str_iis("\"foo:bar\"").unwrap()
+    );
+    assert_eq!(
+        Err(EntityTagError::MissingClosingDoubleQuote),
+        EntityTag::from_str_iis("\"86c41a6a7a9be1:0")
+    );
+}
+
+#[test]
+fn from_header_line() {
+    assert_eq!(
+        EntityTag::with_str(true, "x").unwrap(),
+        EntityTag::from_header_line(b"ETag: W/\"x\"\r\n").unwrap()
+    );
+    assert_eq!(
+        EntityTag::with_str(false, "x").unwrap(),
+        EntityTag::from_header_line("etag:\"x\" \t").unwrap()
+    );
+    assert_eq!(Err(HeaderLineError::InvalidFieldName), EntityTag::from_header_line("ETag \"x\""));
+    assert_eq!(
+        Err(HeaderLineError::InvalidFieldName),
+        EntityTag::from_header_line("Last-Modified: \"x\"")
+    );
+    assert_eq!(
+        Err(HeaderLineError::InvalidEntityTag(EntityTagError::InvalidTag)),
+        EntityTag::from_header_line(b"ETag: \"\xff\"\r\n")
+    );
+}
+
+#[test]
+fn to_header_string() {
+    let etag = EntityTag::with_str(true, "x").unwrap();
+
+    assert_eq!("ETag: W/\"x\"", etag.to_header_string());
+    assert_eq!("ETag: W/\"x\"\r\n", etag.to_header_line());
+    assert_eq!(etag, EntityTag::from_header_line(&etag.to_header_line()).unwrap());
+}
+
+#[test]
+fn list_iter_from_values() {
+    let items = EntityTagList::iter_from_values(vec!["\"a\", W/\"b\"", " \"c\" "])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            EntityTagListItem::Tag(EntityTag::with_str(false, "a").unwrap()),
+            EntityTagListItem::Tag(EntityTag::with_str(true, "b").unwrap()),
+            EntityTagListItem::Tag(EntityTag::with_str(false, "c").unwrap()),
+        ],
+        items
+    );
+
+    let mut iter = EntityTagList::iter_from_values(vec!["*"]);
+    assert_eq!(Some(Ok(EntityTagListItem::Any)), iter.next());
+    assert_eq!(None, iter.next());
+
+    let mut iter = EntityTagList::iter_from_values(vec!["\"a\"", "*", "\"b\""]);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(Some(Err(EntityTagError::InvalidTag)), iter.next());
+    assert_eq!(None, iter.next());
+
+    let mut iter = EntityTagList::iter_from_values(vec!["\"a\" \"b\""]);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(Some(Err(EntityTagError::InvalidTag)), iter.next());
+}
+
+#[test]
+fn list_parse_lenient() {
+    let list = EntityTagList::Tags(vec![
+        EntityTag::with_str(false, "a").unwrap(),
+        EntityTag::with_str(true, "b").unwrap(),
+    ]);
+
+    assert_eq!(list, EntityTagList::from_str_lenient(", \"a\", , W/\"b\",\t").unwrap());
+    assert_eq!(list, EntityTagList::from_string_lenient("\"a\",,W/\"b\"").unwrap());
+    assert_eq!(EntityTagList::Tags(vec![]), EntityTagList::from_str_lenient(" , ").unwrap());
+    assert_eq!(EntityTagList::Any, EntityTagList::from_str_lenient("*").unwrap());
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTagList::from_str_lenient("\"a\" \"b\""));
+    assert_eq!(
+        Err(EntityTagError::MissingStartingDoubleQuote),
+        EntityTagList::from_str("\"a\", , \"b\"")
+    );
+}
+
+#[test]
+fn list_fmt() {
+    assert_eq!("*", EntityTagList::Any.to_string());
+    assert_eq!(
+        "\"a\", W/\"b\"",
+        EntityTagList::from_str_lenient(" \"a\",,W/\"b\" ").unwrap().to_string()
+    );
+    assert_eq!("", EntityTagList::Tags(vec![]).to_string());
+}
+
+#[test]
+fn list_collect() {
+    let list = EntityTagList::from_str("\"a\", W/\"b\", \"c\"").unwrap();
+
+    let weak: Option<EntityTagList> = match list {
+        EntityTagList::Tags(tags) => tags.into_iter().filter(|etag| etag.weak).collect(),
+        EntityTagList::Any => Some(EntityTagList::Any),
+    };
+    let mut weak = weak.unwrap();
+    assert_eq!("W/\"b\"", weak.to_string());
+
+    // an empty list is not a valid field value
+    assert_eq!(None, Vec::<EntityTag>::new().into_iter().collect::<Option<EntityTagList>>());
+
+    weak.extend(vec![EntityTag::with_str(false, "d").unwrap()]);
+    assert_eq!("W/\"b\", \"d\"", weak.to_string());
+
+    let mut any = EntityTagList::Any;
+    any.extend(vec![EntityTag::with_str(false, "d").unwrap()]);
+    assert_eq!(EntityTagList::Any, any);
+}
+
+#[test]
+fn list_dedup_normalize() {
+    let mut list =
+        EntityTagList::from_str("\"b\", W/\"a\", \"b\", \"a\", W/\"c\", W/\"c\"").unwrap();
+    list.dedup();
+    assert_eq!("\"b\", \"a\", W/\"c\"", list.to_string());
+
+    let mut list = EntityTagList::from_str("W/\"c\", \"b\", W/\"a\", \"b\"").unwrap();
+    list.normalize();
+    assert_eq!("W/\"a\", \"b\", W/\"c\"", list.to_string());
+
+    let mut any = EntityTagList::Any;
+    any.normalize();
+    assert_eq!(EntityTagList::Any, any);
+}
+
+#[test]
+fn list_contains() {
+    let list = EntityTagList::from_str("\"a\", W/\"b\"").unwrap();
+
+    assert!(list.contains_strong(&EntityTag::with_str(false, "a").unwrap()));
+    assert!(!list.contains_strong(&EntityTag::with_str(true, "a").unwrap()));
+    assert!(!list.contains_strong(&EntityTag::with_str(false, "b").unwrap()));
+    assert!(list.contains_weak(&EntityTag::with_str(false, "b").unwrap()));
+    assert!(list.contains_weak(&EntityTag::with_str(true, "a").unwrap()));
+    assert!(!list.contains_weak(&EntityTag::with_str(false, "c").unwrap()));
+    assert!(EntityTagList::Any.contains_strong(&EntityTag::with_str(true, "c").unwrap()));
+}
+
+#[test]
+fn binary_encoding() {
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    let bytes = etag.to_bytes();
+    assert_eq!(b"\x01\x03foo", bytes.as_slice());
+    assert_eq!((etag, 5), EntityTag::from_bytes_encoded(&bytes).unwrap());
+
+    let tag = "x".repeat(300);
+    let etag = EntityTag::with_str(false, &tag).unwrap();
+
+    let mut bytes = etag.to_bytes();
+    assert_eq!(&[0x00, 0xAC, 0x02], &bytes[..3]);
+    bytes.extend_from_slice(b"trailing");
+    assert_eq!((etag, 303), EntityTag::from_bytes_encoded(&bytes).unwrap());
+
+    assert_eq!(Err(BinaryDecodeError::UnexpectedEnd), EntityTag::from_bytes_encoded(b""));
+    assert_eq!(Err(BinaryDecodeError::InvalidFlags), EntityTag::from_bytes_encoded(b"\x02\x00"));
+    assert_eq!(Err(BinaryDecodeError::UnexpectedEnd), EntityTag::from_bytes_encoded(b"\x00\x03fo"));
+    assert_eq!(
+        Err(BinaryDecodeError::InvalidLength),
+        EntityTag::from_bytes_encoded(b"\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01")
+    );
+    assert_eq!(
+        Err(BinaryDecodeError::InvalidEntityTag(EntityTagError::InvalidTag)),
+        EntityTag::from_bytes_encoded(b"\x00\x01\"")
+    );
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn json_schema() {
+    let schema = schemars::schema_for!(EntityTag);
+
+    assert_eq!(Some("string"), schema.get("type").and_then(|v| v.as_str()));
+    assert_eq!(
+        Some(r#"^(W/)?"[^"\u0000- \u007F]*"$"#),
+        schema.get("pattern").and_then(|v| v.as_str())
+    );
+}
+
+#[cfg(feature = "utoipa")]
+#[test]
+fn openapi() {
+    use entity_tag::openapi::{etag_header, IfMatch, IfNoneMatch};
+    use utoipa::{
+        openapi::{path::ParameterIn, RefOr, Required, Schema},
+        IntoParams, PartialSchema, ToSchema,
+    };
+
+    assert_eq!("EntityTag", EntityTag::name());
+
+    match EntityTag::schema() {
+        RefOr::T(Schema::Object(object)) => {
+            assert_eq!(Some(r#"^(W/)?"[^"\u0000- \u007F]*"$"#), object.pattern.as_deref())
+        },
+        _ => panic!("expected an object schema"),
+    }
+
+    let params = IfMatch::into_params(|| None);
+    assert_eq!(1, params.len());
+    assert_eq!("If-Match", params[0].name);
+    assert!(params[0].parameter_in == ParameterIn::Header);
+    assert!(params[0].required == Required::False);
+
+    let params = IfNoneMatch::into_params(|| Some(ParameterIn::Query));
+    assert_eq!("If-None-Match", params[0].name);
+    assert!(params[0].parameter_in == ParameterIn::Header);
+
+    assert!(etag_header().description.is_some());
+}
+
+#[cfg(feature = "sqlx")]
+#[test]
+fn sqlx() {
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+
+        sqlx::query("CREATE TABLE documents (etag TEXT NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let etag = EntityTag::with_str(true, "foo").unwrap();
+
+        sqlx::query("INSERT INTO documents VALUES (?), ('foo')")
+            .bind(&etag)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let rows = sqlx::query("SELECT etag FROM documents").fetch_all(&mut conn).await.unwrap();
+
+        assert_eq!("W/\"foo\"", rows[0].get::<String, _>(0));
+        assert_eq!(etag, rows[0].get::<EntityTag, _>(0));
+        assert!(rows[1].try_get::<EntityTag, _>(0).is_err());
+    });
+}
+
+#[cfg(feature = "rusqlite")]
+#[test]
+fn rusqlite() {
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+
+    conn.execute("CREATE TABLE documents (etag TEXT NOT NULL)", []).unwrap();
+
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    conn.execute("INSERT INTO documents VALUES (?1), ('foo')", [&etag]).unwrap();
+
+    let mut statement = conn.prepare("SELECT etag FROM documents").unwrap();
+    let mut rows = statement.query([]).unwrap();
+
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!("W/\"foo\"", row.get::<_, String>(0).unwrap());
+    assert_eq!(etag, row.get::<_, EntityTag>(0).unwrap());
+
+    let row = rows.next().unwrap().unwrap();
+    assert!(row.get::<_, EntityTag>(0).is_err());
+}
+
+#[test]
+fn row_version_tag() {
+    use entity_tag::RowVersionTag;
+
+    assert_eq!("\"2a\"", 42u32.to_entity_tag().to_string());
+    assert_eq!(42u32.to_entity_tag(), 42u64.to_entity_tag());
+    assert_eq!(42u64.to_entity_tag(), 42i64.to_entity_tag());
+    assert_eq!("\"ffffffffffffffff\"", (-1i64).to_entity_tag().to_string());
+
+    #[cfg(feature = "std")]
+    {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let time = UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789);
+
+        assert_eq!(1_700_000_000_123_456_789u64.to_entity_tag(), time.to_entity_tag());
+        assert_eq!(
+            (-1_000_000_000i64).to_entity_tag(),
+            (UNIX_EPOCH - Duration::from_secs(1)).to_entity_tag()
+        );
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn fuzz_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use entity_tag::fuzz::EntityTagInput;
+
+    let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+
+    let mut malformed = 0;
+
+    for chunk in data.chunks(32) {
+        let mut u = Unstructured::new(chunk);
+
+        let etag = EntityTag::arbitrary(&mut u).unwrap();
+        assert_eq!(etag, EntityTag::from_string(etag.to_string()).unwrap());
+
+        let mut u = Unstructured::new(chunk);
+
+        let list = EntityTagList::arbitrary(&mut u).unwrap();
+        assert_eq!(list, EntityTagList::from_string(list.to_string()).unwrap());
+
+        let mut u = Unstructured::new(chunk);
+
+        let EntityTagInput(s) = EntityTagInput::arbitrary(&mut u).unwrap();
+
+        if EntityTag::from_str(&s).is_err() {
+            malformed += 1;
+        }
+    }
+
+    assert!(malformed > 0);
+}
+
+#[cfg(feature = "proptest")]
+mod strategy {
+    use entity_tag::{strategy, EntityTag, EntityTagList};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn entity_tag_round_trip(etag in strategy::entity_tag()) {
+            prop_assert_eq!(&etag, &EntityTag::from_string(etag.to_string()).unwrap());
+        }
+
+        #[test]
+        fn entity_tag_list_field_value(value in strategy::entity_tag_list_field_value()) {
+            let list = EntityTagList::from_str(&value).unwrap();
+
+            prop_assert_eq!(&value, &list.to_string());
+        }
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_util() {
+    use entity_tag::{assert_no_match, assert_strong_match, assert_weak_match, test_util};
+
+    let etag = EntityTag::from_str("\"foo\"").unwrap();
+
+    assert_strong_match!(etag, EntityTag::with_str(false, "foo").unwrap());
+    assert_weak_match!(etag, EntityTag::with_str(true, "foo").unwrap());
+    assert_no_match!(etag, EntityTag::with_str(false, "bar").unwrap());
+
+    assert_eq!("\"\"", test_util::empty().to_string());
+    assert_eq!(998, test_util::long().get_tag().len());
+
+    for etag in test_util::edge_cases() {
+        assert_eq!(etag, EntityTag::from_string(etag.to_string()).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    {
+        let metadata = std::fs::File::open("tests/data/P1060382.JPG").unwrap().metadata().unwrap();
+
+        let fake = test_util::FakeMetadata::new()
+            .len(metadata.len())
+            .modified(metadata.modified().unwrap());
+
+        assert_eq!(EntityTag::from_file_meta(&metadata), fake.to_entity_tag());
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic]
+fn test_util_assert_strong_match() {
+    entity_tag::assert_strong_match!(
+        EntityTag::with_str(true, "foo").unwrap(),
+        EntityTag::with_str(true, "foo").unwrap()
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_events() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use tracing::{span, Event, Level, Metadata, Subscriber};
+
+    struct Counter(Arc<AtomicUsize>);
+
+    impl Subscriber for Counter {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() == Level::DEBUG
+        }
+
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, _event: &Event<'_>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    let count = Arc::new(AtomicUsize::new(0));
+
+    tracing::subscriber::with_default(Counter(count.clone()), || {
+        let list = EntityTagList::from_str("\"foo\"").unwrap();
+        let current = EntityTag::from_str("W/\"foo\"").unwrap();
+
+        assert!(list.check_if_match(Some(&current)).is_err());
+        assert!(list.check_if_none_match(Some(&current)).is_err());
+    });
+
+    assert_eq!(2, count.load(Ordering::Relaxed));
+}
+
+#[cfg(feature = "ufmt")]
+#[test]
+fn write_to_uwrite() {
+    struct Buffer(Vec<u8>);
+
+    impl ufmt_write::uWrite for Buffer {
+        type Error = ();
+
+        fn write_str(&mut self, s: &str) -> Result<(), ()> {
+            self.0.extend_from_slice(s.as_bytes());
+
+            Ok(())
+        }
+    }
+
+    let mut buffer = Buffer(Vec::new());
+
+    EntityTag::with_str(true, "foo").unwrap().write_to_uwrite(&mut buffer).unwrap();
+    EntityTag::with_str(false, "bar").unwrap().write_to_uwrite(&mut buffer).unwrap();
+
+    assert_eq!(b"W/\"foo\"\"bar\"", buffer.0.as_slice());
+}
+
+#[test]
+fn write_to_slice() {
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    let mut buf = [0u8; 16];
+
+    assert_eq!(Ok(7), etag.write_to_slice(&mut buf));
+    assert_eq!(b"W/\"foo\"", &buf[..7]);
+
+    assert_eq!(Ok(5), EntityTag::with_str(false, "foo").unwrap().write_to_slice(&mut buf));
+    assert_eq!(b"\"foo\"", &buf[..5]);
+
+    let error = etag.write_to_slice(&mut buf[..6]).unwrap_err();
+    assert_eq!(7, error.required());
+
+    #[cfg(feature = "heapless")]
+    {
+        assert_eq!("W/\"foo\"", etag.to_heapless_string::<7>().unwrap().as_str());
+        assert_eq!(7, etag.to_heapless_string::<6>().unwrap_err().required());
+    }
+}
+
+#[test]
+fn entity_tag_ref() {
+    use entity_tag::EntityTagRef;
+
+    let etag = EntityTagRef::from_str("W/\"foo\"").unwrap();
+
+    assert!(etag.weak);
+    assert_eq!("foo", etag.get_tag());
+    assert_eq!("W/\"foo\"", etag.to_string());
+
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTagRef::from_str("foo"));
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTagRef::with_str(false, "fo\"o"));
+
+    let strong = EntityTagRef::with_str(false, "\"foo\"").unwrap();
+
+    assert!(strong.weak_eq(&etag));
+    assert!(strong.strong_ne(&etag));
+    assert!(strong.strong_eq(&EntityTagRef::with_str(false, "foo").unwrap()));
+
+    let mut buf = [0u8; 8];
+    assert_eq!(Ok(7), etag.write_to_slice(&mut buf));
+    assert_eq!(b"W/\"foo\"", &buf[..7]);
+
+    let owned = EntityTag::from(etag);
+    assert_eq!(EntityTag::from_str("W/\"foo\"").unwrap(), owned);
+    assert_eq!(etag, owned.as_entity_tag_ref());
+}
+
+#[cfg(all(feature = "embedded-io", feature = "generate"))]
+#[test]
+fn from_reader() {
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+    let mut reader = data.as_slice();
+
+    assert_eq!(EntityTag::from_data(&data), EntityTag::from_reader(&mut reader).unwrap());
+    assert_eq!(EntityTag::from_data(&[]), EntityTag::from_reader(&mut &b""[..]).unwrap());
+}
+
+#[cfg(feature = "capi")]
+#[test]
+fn capi() {
+    use entity_tag::capi::*;
+
+    unsafe {
+        let a = entity_tag_parse(b"W/\"foo\"".as_ptr(), 7);
+        let b = entity_tag_new(false, b"foo".as_ptr(), 3);
+
+        assert!(!a.is_null() && !b.is_null());
+        assert!(entity_tag_is_weak(a));
+        assert!(entity_tag_weak_eq(a, b));
+        assert!(!entity_tag_strong_eq(a, b));
+
+        assert!(entity_tag_parse(b"foo".as_ptr(), 3).is_null());
+        assert!(entity_tag_new(false, b"\xFF".as_ptr(), 1).is_null());
+
+        assert_eq!(7, entity_tag_write(a, std::ptr::null_mut(), 0));
+
+        let mut buf = [0u8; 7];
+        assert_eq!(7, entity_tag_write(a, buf.as_mut_ptr(), buf.len()));
+        assert_eq!(b"W/\"foo\"", &buf);
+
+        let c = entity_tag_from_data(b"foo".as_ptr(), 3);
+        assert_eq!(13, entity_tag_write(c, buf.as_mut_ptr(), buf.len()));
+        assert_eq!(b"W/\"foo\"", &buf);
+
+        entity_tag_free(a);
+        entity_tag_free(b);
+        entity_tag_free(c);
+        entity_tag_free(std::ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm() {
+    use entity_tag::wasm::WasmEntityTag;
+
+    let a = WasmEntityTag::parse("W/\"foo\"").unwrap();
+    let b = WasmEntityTag::new(false, "foo").unwrap();
+
+    assert!(a.weak());
+    assert_eq!("foo", a.tag());
+    assert!(a.weak_eq(&b));
+    assert!(!a.strong_eq(&b));
+    assert_eq!("W/\"foo\"", a.to_string());
+    assert_eq!(EntityTag::from_data(b"foo"), WasmEntityTag::from_data(b"foo").into_inner());
+}
+
+#[test]
+fn display_padding() {
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    assert_eq!("W/\"foo\"   ", format!("{:10}", etag));
+    assert_eq!("   W/\"foo\"", format!("{:>10}", etag));
+    assert_eq!("-W/\"foo\"--", format!("{:-^10}", etag));
+    assert_eq!("W/\"foo\"", format!("{:3}", etag));
+    assert_eq!("\"é\"  ", format!("{:5}", EntityTag::with_str(false, "é").unwrap()));
+    assert_eq!("  \"foo\"", format!("{:>7}", EntityTagRef::with_str(false, "foo").unwrap()));
+
+    let list = EntityTagList::from_str("\"a\", W/\"b\"").unwrap();
+
+    assert_eq!("\"a\", W/\"b\"", format!("{:20}", list));
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn hash() {
+    let etag = EntityTag::from_data(&[102, 111, 111]);
+    let hash = etag.as_hash().unwrap();
+
+    assert_eq!(etag, EntityTag::from_hash(false, hash));
+    assert_eq!("W/\"972Sf7Z4eu8\"", EntityTag::from_hash(true, hash).to_string());
+
+    assert_eq!(None, EntityTag::with_str(false, "foo").unwrap().as_hash());
+    assert_eq!(None, EntityTag::with_str(false, "972Sf7Z4eu9").unwrap().as_hash());
+    assert_eq!(None, EntityTag::with_str(false, "972Sf7Z4eu-").unwrap().as_hash());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn try_from_header_value() {
+    use std::convert::{TryFrom, TryInto};
+
+    use http::HeaderValue;
+
+    let value = HeaderValue::from_static("W/\"foo\"");
+
+    let etag: EntityTag = (&value).try_into().unwrap();
+    assert_eq!(EntityTag::with_str(true, "foo").unwrap(), etag);
+
+    let value = HeaderValue::from_bytes(b"\"\xC3\xA9\"").unwrap();
+    assert_eq!("é", EntityTag::try_from(value).unwrap().get_tag());
+
+    assert_eq!(
+        Err(EntityTagError::MissingStartingDoubleQuote),
+        EntityTag::try_from(HeaderValue::from_static("foo"))
+    );
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        EntityTag::try_from(HeaderValue::from_bytes(b"\"\xFF\"").unwrap())
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn into_header_value() {
+    use std::convert::TryFrom;
+
+    use http::HeaderValue;
+
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    assert_eq!(HeaderValue::from_static("W/\"foo\""), HeaderValue::from(&etag));
+
+    let value: HeaderValue = EntityTag::with_str(false, "é").unwrap().into();
+    assert_eq!(b"\"\xC3\xA9\"", value.as_bytes());
+
+    // an unchecked constructor cannot inject header fields
+    let injected = unsafe { EntityTag::with_str_unchecked(false, "a\r\nSet-Cookie: b") };
+    assert_eq!(HeaderValue::from_static(""), HeaderValue::from(&injected));
+
+    let injected = unsafe { EntityTagBytes::new_unchecked(false, b"a\r\nb"[..].into()) };
+    assert_eq!(HeaderValue::from_static(""), HeaderValue::from(&injected));
+
+    let list = EntityTagList::from_str("\"a\", W/\"b\"").unwrap();
+    assert_eq!(HeaderValue::from_static("\"a\", W/\"b\""), HeaderValue::try_from(&list).unwrap());
+    assert_eq!(HeaderValue::from_static("*"), HeaderValue::try_from(&EntityTagList::Any).unwrap());
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        HeaderValue::try_from(&EntityTagList::from_str_lenient(",").unwrap())
+    );
+}
+
+#[cfg(feature = "headers")]
+#[test]
+fn typed_headers() {
+    use std::convert::TryFrom;
+
+    use headers::{ETag, IfMatch, IfNoneMatch};
+
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    let typed = ETag::try_from(&etag).unwrap();
+    assert_eq!("W/\"foo\"".parse::<ETag>().unwrap(), typed);
+    assert_eq!(etag, EntityTag::try_from(typed).unwrap());
+
+    let list = EntityTagList::from_str("\"a\", W/\"b\"").unwrap();
+
+    let if_none_match = IfNoneMatch::try_from(&list).unwrap();
+    assert!(!if_none_match.precondition_passes(&"\"b\"".parse().unwrap()));
+    assert!(if_none_match.precondition_passes(&"\"c\"".parse().unwrap()));
+    assert_eq!(list, EntityTagList::try_from(if_none_match).unwrap());
+
+    assert_eq!(IfNoneMatch::any(), IfNoneMatch::try_from(EntityTagList::Any).unwrap());
+    assert_eq!(IfMatch::any(), IfMatch::try_from(EntityTagList::Any).unwrap());
+    assert_eq!(EntityTagList::Any, EntityTagList::try_from(IfMatch::any()).unwrap());
+
+    let if_match = IfMatch::try_from(&list).unwrap();
+    assert!(if_match.precondition_passes(&"\"a\"".parse().unwrap()));
+    assert!(!if_match.precondition_passes(&"W/\"b\"".parse().unwrap()));
+
+    // invalid values are rejected instead of panicking
+    let injected = unsafe { EntityTag::with_str_unchecked(false, "a\r\nb") };
+    assert_eq!(Err(EntityTagError::InvalidTag), ETag::try_from(&injected));
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        IfMatch::try_from(&EntityTagList::from_str_lenient("").unwrap())
+    );
+}
+
+#[cfg(feature = "tonic")]
+#[test]
+fn grpc_metadata() {
+    use std::convert::TryFrom;
+
+    use tonic::metadata::{MetadataMap, MetadataValue};
+
+    let etag = EntityTag::with_str(true, "foo").unwrap();
+
+    let mut metadata = MetadataMap::new();
+
+    grpc_metadata::insert_etag(&mut metadata, &etag);
+    assert_eq!("W/\"foo\"", metadata.get(grpc_metadata::ETAG).unwrap());
+    assert_eq!(Some(etag.clone()), grpc_metadata::get_etag(&metadata).unwrap());
+
+    metadata.append(grpc_metadata::IF_NONE_MATCH, MetadataValue::from_static("\"a\""));
+    metadata.append(grpc_metadata::IF_NONE_MATCH, MetadataValue::from_static("W/\"foo\""));
+
+    let if_none_match = grpc_metadata::get_if_none_match(&metadata).unwrap().unwrap();
+    assert!(if_none_match.check_if_none_match(Some(&etag)).is_err());
+    assert_eq!(None, grpc_metadata::get_if_match(&metadata).unwrap());
+
+    metadata.insert(grpc_metadata::IF_MATCH, MetadataValue::from_static("\"a"));
+    assert!(grpc_metadata::get_if_match(&metadata).is_err());
+
+    let obs_text = EntityTag::with_str(false, "é").unwrap();
+    assert_eq!(obs_text, EntityTag::try_from(&MetadataValue::from(&obs_text)).unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_file_meta_actix_files() {
+    use std::{fs, time::UNIX_EPOCH};
+
+    let metadata = fs::metadata("tests/data/P1060382.JPG").unwrap();
+
+    let etag = EntityTag::from_file_meta_actix_files(&metadata).unwrap();
+
+    let time = metadata.modified().unwrap().duration_since(UNIX_EPOCH).unwrap();
+
+    #[cfg(unix)]
+    let ino = std::os::unix::fs::MetadataExt::ino(&metadata);
+    #[cfg(not(unix))]
+    let ino = 0u64;
+
+    assert!(!etag.weak);
+    assert_eq!(
+        format!("{:x}:{:x}:{:x}:{:x}", ino, metadata.len(), time.as_secs(), time.subsec_nanos()),
+        etag.get_tag()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cache_policy() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let etag = EntityTag::with_str(false, "foo").unwrap();
+
+    let policy = CachePolicy::new(etag.clone())
+        .public()
+        .max_age(60)
+        .stale_while_revalidate(30)
+        .last_modified(UNIX_EPOCH + Duration::from_secs(784111777))
+        .vary("Accept")
+        .vary("Accept-Encoding");
+
+    assert_eq!(&etag, policy.etag());
+    assert_eq!("public, max-age=60, stale-while-revalidate=30", policy.cache_control());
+    assert_eq!(
+        vec![
+            ("etag", String::from("\"foo\"")),
+            ("cache-control", String::from("public, max-age=60, stale-while-revalidate=30")),
+            ("last-modified", String::from("Sun, 06 Nov 1994 08:49:37 GMT")),
+            ("vary", String::from("Accept, Accept-Encoding")),
+        ],
+        policy.to_headers()
+    );
+
+    let policy = CachePolicy::new(etag).last_modified(UNIX_EPOCH + Duration::from_secs(951782400));
+
+    assert_eq!(
+        vec![
+            ("etag", String::from("\"foo\"")),
+            ("last-modified", String::from("Tue, 29 Feb 2000 00:00:00 GMT")),
+        ],
+        policy.to_headers()
+    );
+
+    #[cfg(feature = "http")]
+    {
+        use std::convert::TryFrom;
+
+        let mut headers = http::HeaderMap::new();
+
+        headers.insert(http::header::CACHE_CONTROL, http::HeaderValue::from_static("no-store"));
+
+        CachePolicy::new(EntityTag::with_str(true, "bar").unwrap())
+            .no_cache()
+            .private()
+            .apply_to(&mut headers);
+
+        assert_eq!("W/\"bar\"", headers.get(http::header::ETAG).unwrap());
+        assert_eq!("private, no-cache", headers.get(http::header::CACHE_CONTROL).unwrap());
+
+        // obs-text
+        let etag = EntityTag::with_str(false, "é").unwrap();
+
+        CachePolicy::new(etag.clone()).apply_to(&mut headers);
+
+        assert_eq!(etag, EntityTag::try_from(headers.get(http::header::ETAG).unwrap()).unwrap());
+    }
+}
+
+#[test]
+fn revalidation() {
+    assert!(!Revalidation::new().is_conditional());
+    assert!(Revalidation::new().to_headers().is_empty());
+
+    let revalidation = Revalidation::new()
+        .etag(EntityTag::with_str(false, "a").unwrap())
+        .etag(EntityTag::with_str(true, "b").unwrap())
+        .last_modified("Sun, 06 Nov 1994 08:49:37 GMT");
+
+    assert!(revalidation.is_conditional());
+    assert_eq!(
+        vec![
+            ("if-none-match", String::from("\"a\", W/\"b\"")),
+            ("if-modified-since", String::from("Sun, 06 Nov 1994 08:49:37 GMT")),
+        ],
+        revalidation.to_headers()
+    );
+
+    #[cfg(feature = "std")]
+    {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let revalidation =
+            Revalidation::new().last_modified_time(UNIX_EPOCH + Duration::from_secs(784111777));
+
+        assert_eq!(
+            vec![("if-modified-since", String::from("Sun, 06 Nov 1994 08:49:37 GMT"))],
+            revalidation.to_headers()
+        );
+    }
+
+    #[cfg(feature = "http")]
+    {
+        let mut headers = http::HeaderMap::new();
+
+        revalidation.apply_to(&mut headers);
+
+        let if_none_match = http_header::get_if_none_match(&headers).unwrap().unwrap();
+        assert!(if_none_match.contains_weak(&EntityTag::with_str(false, "b").unwrap()));
+        assert_eq!(
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            headers.get(http::header::IF_MODIFIED_SINCE).unwrap()
+        );
+
+        // obs-text
+        let etag = EntityTag::with_str(false, "é").unwrap();
+
+        Revalidation::new().etag(etag.clone()).apply_to(&mut headers);
+
+        assert_eq!(
+            vec![etag].into_iter().collect::<Option<EntityTagList>>().unwrap(),
+            http_header::get_if_none_match(&headers).unwrap().unwrap()
+        );
+    }
+}
+
+#[test]
+fn satisfies_preconditions() {
+    let etag = EntityTag::with_str(false, "b").unwrap();
+    let weak = EntityTag::with_str(true, "b").unwrap();
+
+    assert!(etag.satisfies_if_match("\"a\", \"b\""));
+    assert!(etag.satisfies_if_match("*"));
+    assert!(!etag.satisfies_if_match("\"a\", W/\"b\""));
+    assert!(!weak.satisfies_if_match("\"b\""));
+    assert!(!etag.satisfies_if_match("\"b"));
+
+    assert!(!etag.satisfies_if_none_match("\"a\", W/\"b\""));
+    assert!(!weak.satisfies_if_none_match("\"b\""));
+    assert!(!etag.satisfies_if_none_match(" * "));
+    assert!(etag.satisfies_if_none_match("\"a\""));
+    assert!(etag.satisfies_if_none_match("\"b"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn strong_compared() {
+    use std::collections::HashMap;
+
+    assert_eq!(None, StrongCompared::new(EntityTag::with_str(true, "a").unwrap()));
+
+    let a = StrongCompared::new(EntityTag::with_str(false, "a").unwrap()).unwrap();
+
+    assert!(a.matches(&EntityTag::with_str(false, "a").unwrap()));
+    assert!(!a.matches(&EntityTag::with_str(true, "a").unwrap()));
+    assert_eq!("\"a\"", a.to_string());
+    assert_eq!("a", a.get_tag());
+
+    let mut ranges = HashMap::new();
+
+    ranges.insert(a.clone(), 1);
+
+    let key = StrongCompared::new(EntityTag::from_str("\"a\"").unwrap()).unwrap();
+    assert_eq!(Some(&1), ranges.get(&key));
+    assert_eq!(EntityTag::with_str(false, "a").unwrap(), key.into_inner());
+}
+
+#[test]
+fn canonicalize() {
+    assert_eq!("W/\"xyzzy\"", EntityTag::canonicalize(" w/\"xyzzy\"\t").unwrap());
+    assert_eq!("W/\"xyzzy\"", EntityTag::canonicalize("W/\"xyzzy\"").unwrap());
+    assert_eq!("\"xyzzy\"", EntityTag::canonicalize("xyzzy").unwrap());
+    assert_eq!("\"\"", EntityTag::canonicalize("\"\"").unwrap());
+
+    assert_eq!(Err(EntityTagError::MissingClosingDoubleQuote), EntityTag::canonicalize("\"xyzzy"));
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTag::canonicalize("xy zzy"));
+}
+
+#[test]
+fn lowercase_weak() {
+    let options = ParseOptions::new().lowercase_weak(true);
+
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTag::from_str("w/\"foo\""));
+
+    let etag = EntityTag::from_str_with("w/\"foo\"", options).unwrap();
+    assert!(etag.weak);
+    assert_eq!("W/\"foo\"", etag.to_string());
+
+    let etag = EntityTag::from_string_with(String::from("w/\"foo\""), options).unwrap();
+    assert_eq!(EntityTag::with_str(true, "foo").unwrap(), etag);
+
+    assert_eq!(
+        EntityTag::with_str(false, "bar").unwrap(),
+        EntityTag::from_string_with("\"bar\"", options).unwrap()
+    );
+    assert_eq!(
+        EntityTagRef::with_str(true, "foo").unwrap(),
+        EntityTagRef::from_str_with("w/\"foo\"", options).unwrap()
+    );
+}
+
+#[test]
+fn missing_quotes() {
+    let options = ParseOptions::new().missing_quotes(true);
+
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTag::from_str("abc"));
+
+    assert_eq!(
+        EntityTag::with_str(false, "abc").unwrap(),
+        EntityTag::from_str_with("abc", options).unwrap()
+    );
+    assert_eq!(
+        EntityTag::with_str(true, "abc").unwrap(),
+        EntityTag::from_string_with(String::from("W/\"abc\""), options).unwrap()
+    );
+    assert_eq!(
+        Err(EntityTagError::MissingClosingDoubleQuote),
+        EntityTag::from_str_with("\"abc", options)
+    );
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTag::from_str_with("a c", options));
+}
+
+#[test]
+fn trim_ows() {
+    let options = ParseOptions::new().trim_ows(true);
+
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTag::from_str(" \"abc\""));
+
+    assert_eq!(
+        EntityTag::with_str(false, "abc").unwrap(),
+        EntityTag::from_str_with(" \"abc\"\t", options).unwrap()
+    );
+    assert_eq!(
+        EntityTag::with_str(true, "abc").unwrap(),
+        EntityTag::from_string_with(String::from("\tW/\"abc\"  "), options).unwrap()
+    );
+    assert_eq!(
+        EntityTag::with_str(true, "abc").unwrap(),
+        EntityTag::from_str_with(" w/\"abc\" ", options.lowercase_weak(true)).unwrap()
+    );
+    assert_eq!(
+        Err(EntityTagError::MissingStartingDoubleQuote),
+        EntityTag::from_str_with("W/ \"abc\"", options)
+    );
+}
+
+#[test]
+fn entity_tag_bytes() {
+    let etag = EntityTagBytes::from_bytes(b"W/\"a\xFFb\"").unwrap();
+
+    assert!(etag.weak);
+    assert_eq!(b"a\xFFb", etag.get_tag());
+    assert_eq!(b"W/\"a\xFFb\"".to_vec(), etag.to_header_bytes());
+    assert_eq!("W/\"a\\xFFb\"", etag.to_string());
+    assert_eq!("\"a\\\\xFFb\"", EntityTagBytes::from_bytes(b"\"a\\xFFb\"").unwrap().to_string());
+    assert_eq!(Err(EntityTagError::InvalidTag), etag.to_entity_tag());
+
+    let strong = EntityTagBytes::with_bytes(false, b"\"a\xFFb\"").unwrap();
+
+    assert!(strong.weak_eq(&etag));
+    assert!(strong.strong_ne(&etag));
+    assert!(strong.strong_eq(&EntityTagBytes::with_bytes(false, b"a\xFFb").unwrap()));
+
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTagBytes::with_bytes(false, b"a b"));
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), EntityTagBytes::from_bytes(b"a"));
+    assert_eq!(Err(EntityTagError::MissingClosingDoubleQuote), EntityTagBytes::from_bytes(b"\"a"));
+
+    let utf8 = EntityTagBytes::from(EntityTag::with_str(false, "é").unwrap());
+
+    assert_eq!("\"é\"", utf8.to_string());
+    assert_eq!(EntityTag::with_str(false, "é").unwrap(), utf8.to_entity_tag().unwrap());
+    assert_eq!(utf8, utf8.clone().into_owned());
+
+    #[cfg(feature = "http")]
+    {
+        use std::convert::TryFrom;
+
+        use http::HeaderValue;
+
+        let value = HeaderValue::from(&etag);
+
+        assert_eq!(b"W/\"a\xFFb\"", value.as_bytes());
+        assert_eq!(etag, EntityTagBytes::try_from(&value).unwrap());
+    }
+}
+
+#[test]
+fn with_str_lossy() {
+    let etag = EntityTag::with_str_lossy(false, "foo");
+    assert_eq!(EntityTag::with_str(false, "foo").unwrap(), etag);
+    assert!(matches!(etag.into_tag(), std::borrow::Cow::Borrowed(_)));
+
+    assert_eq!("W/\"My%20Title%22é\"", EntityTag::with_str_lossy(true, "My Title\"é").to_string());
+    assert_eq!("\"%0A%7F\"", EntityTag::with_str_lossy(false, "\n\x7F").to_string());
+    assert_eq!("\"a%2520b\"", EntityTag::with_str_lossy(false, "a%20b").to_string());
+    assert_eq!("\"\"", EntityTag::with_str_lossy(false, "").to_string());
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn entity_tag_writer() {
+    use std::io::Write;
+
+    let mut writer = EntityTagWriter::new(Vec::new());
+
+    writer.write_all(b"f").unwrap();
+    write!(writer, "oo").unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(EntityTag::from_data(b"foo"), writer.etag());
+
+    let (data, etag) = writer.finish();
+
+    assert_eq!(b"foo".to_vec(), data);
+    assert_eq!(EntityTag::from_data(b"foo"), etag);
+
+    // only the bytes accepted by the inner writer are hashed
+    let mut buf = [0u8; 2];
+    let mut writer = EntityTagWriter::new(&mut buf[..]);
+
+    assert_eq!(2, writer.write(b"foo").unwrap());
+    assert_eq!(EntityTag::from_data(b"fo"), writer.etag());
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn entity_tag_reader() {
+    use std::io::Read;
+
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+    let mut reader = EntityTagReader::new(data.as_slice());
+
+    let mut head = [0u8; 10];
+    reader.read_exact(&mut head).unwrap();
+
+    assert_eq!(EntityTag::from_data(&data[..10]), reader.etag());
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+
+    let (_, etag) = reader.finish();
+
+    assert_eq!(EntityTag::from_data(&data), etag);
+}
+
+#[cfg(all(feature = "futures-core", feature = "generate"))]
+#[test]
+fn entity_tag_stream() {
+    use futures::{executor::block_on, stream, StreamExt};
+
+    let chunks: Vec<Result<&[u8], ()>> = vec![Ok(b"f"), Ok(b"o"), Ok(b"o")];
+
+    let mut stream = EntityTagStream::new(stream::iter(chunks));
+
+    let collected: Vec<_> = block_on((&mut stream).collect());
+
+    assert_eq!(vec![Ok(&b"f"[..]), Ok(b"o"), Ok(b"o")], collected);
+    assert_eq!(EntityTag::from_data(b"foo"), stream.etag());
+}
+
+#[cfg(all(feature = "body", feature = "generate"))]
+#[test]
+fn entity_tag_body() {
+    use futures::executor::block_on;
+    use http_body_util::{BodyExt, Full};
+
+    let mut body = EntityTagStream::new(Full::new(&b"foo"[..]));
+
+    let collected = block_on((&mut body).collect()).unwrap().to_bytes();
+
+    assert_eq!(&b"foo"[..], collected);
+    assert_eq!(EntityTag::from_data(b"foo"), body.etag());
+}
+
+#[cfg(all(feature = "tokio", feature = "generate"))]
+#[tokio::test]
+async fn entity_tag_async_writer() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = EntityTagWriter::new(Vec::new());
+
+    writer.write_all(b"f").await.unwrap();
+    writer.write_all(b"oo").await.unwrap();
+    writer.shutdown().await.unwrap();
+
+    let (data, etag) = writer.finish();
+
+    assert_eq!(b"foo".to_vec(), data);
+    assert_eq!(EntityTag::from_data(b"foo"), etag);
+}
+
+#[cfg(all(feature = "bytes", feature = "generate"))]
+#[test]
+fn hasher_write_buf() {
+    use bytes::{Buf, Bytes};
+
+    let mut buf = Bytes::from_static(b"f").chain(Bytes::from_static(b"oo"));
+
+    let mut hasher = EntityTagHasher::new();
+
+    hasher.write_buf(&mut buf);
+
+    assert!(!buf.has_remaining());
+    assert_eq!(EntityTag::from_data(b"foo"), hasher.finish());
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn hasher_state() {
+    let mut hasher = EntityTagHasher::new();
+
+    hasher.write(b"f");
+
+    let state = hasher.to_state().unwrap();
+
+    let mut hasher = EntityTagHasher::from_state(&state).unwrap();
+
+    hasher.write(b"oo");
+
+    assert_eq!(EntityTag::from_data(b"foo"), hasher.finish());
+
+    let mut state = EntityTagHasher::new().to_state().unwrap();
+
+    assert!(EntityTagHasher::from_state(&state[1..]).is_none());
+
+    state[0] = 0;
+
+    assert!(EntityTagHasher::from_state(&state).is_none());
+}
+
+#[cfg(all(feature = "body", feature = "http", feature = "generate"))]
+#[test]
+fn entity_tag_body_trailer() {
+    use futures::executor::block_on;
+    use http_body_util::{BodyExt, Full};
+
+    let mut body = EntityTagStream::new(Full::new(&b"foo"[..])).with_trailer();
+
+    let collected = block_on((&mut body).collect()).unwrap();
+
+    let etag = EntityTag::from_data(b"foo");
+
+    assert_eq!(etag.to_string(), collected.trailers().unwrap()["etag"]);
+    assert_eq!(&b"foo"[..], collected.to_bytes());
+    assert_eq!(("ETag", etag.to_string()), body.trailer_field());
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn sidecar() {
+    use std::{fs, thread, time::Duration};
+
+    let path = std::env::temp_dir().join(format!("entity-tag-sidecar-{}", std::process::id()));
+    let sidecar_path = EntityTag::sidecar_path(&path);
+
+    assert_eq!(format!("{}.etag", path.display()), sidecar_path.display().to_string());
+
+    fs::write(&path, b"foo").unwrap();
+
+    assert_eq!(None, EntityTag::read_sidecar(&path).unwrap());
+
+    let etag = EntityTag::from_path_with_sidecar(&path).unwrap();
+
+    assert_eq!(EntityTag::from_data(b"foo"), etag);
+    assert_eq!(Some(etag.clone()), EntityTag::read_sidecar(&path).unwrap());
+
+    // a sidecar file which is current is trusted without hashing the file
+    let fake = EntityTag::with_str(false, "fake").unwrap();
+
+    fake.write_sidecar(&path, &fs::metadata(&path).unwrap()).unwrap();
+
+    assert_eq!(fake, EntityTag::from_path_with_sidecar(&path).unwrap());
+
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&path, b"foobar").unwrap();
+
+    assert_eq!(None, EntityTag::read_sidecar(&path).unwrap());
+    assert_eq!(EntityTag::from_data(b"foobar"), EntityTag::from_path_with_sidecar(&path).unwrap());
+
+    fs::write(&sidecar_path, b"malformed").unwrap();
+
+    assert_eq!(None, EntityTag::read_sidecar(&path).unwrap());
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&sidecar_path).unwrap();
+}
+
+#[cfg(all(feature = "xattr", feature = "generate"))]
+#[test]
+fn xattr() {
+    use std::fs;
+
+    let path = std::env::temp_dir().join(format!("entity-tag-xattr-{}", std::process::id()));
+
+    fs::write(&path, b"foo").unwrap();
+
+    // the extended attribute is only a cache, so it works even if the file system does not support it
+    assert_eq!(EntityTag::from_data(b"foo"), EntityTag::from_path_with_xattr(&path).unwrap());
+
+    let fake = EntityTag::with_str(false, "fake").unwrap();
+
+    if fake.write_xattr(&path, &fs::metadata(&path).unwrap()).is_ok() {
+        assert_eq!(Some(fake.clone()), EntityTag::read_xattr(&path).unwrap());
+        assert_eq!(fake, EntityTag::from_path_with_xattr(&path).unwrap());
+
+        fs::write(&path, b"foobar").unwrap();
+
+        assert_eq!(
+            EntityTag::from_data(b"foobar"),
+            EntityTag::from_path_with_xattr(&path).unwrap()
+        );
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn sampled() {
+    let data: Vec<u8> = (0..100u8).collect();
+
+    let etag = EntityTag::from_data_sampled(&data, 10);
+
+    assert!(etag.weak);
+    #[cfg(feature = "std")]
+    assert_eq!(etag, EntityTag::from_reader_sampled(&mut std::io::Cursor::new(&data), 10).unwrap());
+
+    // the middle is not sampled
+    let mut middle_changed = data.clone();
+    middle_changed[50] = 0;
+
+    assert_eq!(etag, EntityTag::from_data_sampled(&middle_changed, 10));
+
+    let mut tail_changed = data.clone();
+    tail_changed[95] = 0;
+
+    assert_ne!(etag, EntityTag::from_data_sampled(&tail_changed, 10));
+    assert_ne!(etag, EntityTag::from_data_sampled(&data[..99], 10));
+
+    // short data is fully sampled
+    #[cfg(feature = "std")]
+    assert_eq!(
+        EntityTag::from_data_sampled(&data[..15], 10),
+        EntityTag::from_reader_sampled(&mut std::io::Cursor::new(&data[..15]), 10).unwrap()
+    );
+    assert_ne!(
+        EntityTag::from_data_sampled(&data[..15], 10),
+        EntityTag::from_data_sampled(&[&data[..12], &[0], &data[13..15]].concat(), 10)
+    );
+}
+
+#[cfg(all(feature = "crc32c", feature = "generate"))]
+#[test]
+fn from_data_crc32c() {
+    let etag = EntityTag::from_data_crc32c(b"Hello world!");
+
+    assert!(etag.weak);
+    assert_eq!("UeeYewwAAAAAAAAA", etag.get_tag());
+    assert_ne!(etag, EntityTag::from_data_crc32c(b"Hello world?"));
+    assert_ne!(EntityTag::from_data_crc32c(b""), EntityTag::from_data_crc32c(b"\0"));
+}
+
+#[cfg(all(feature = "siphasher", feature = "generate"))]
+#[test]
+fn from_data_siphash() {
+    use std::hash::Hasher;
+
+    let key = [1u8; 16];
+
+    let etag = EntityTag::from_data_siphash13(&key, b"foo");
+
+    assert!(!etag.weak);
+    assert_eq!(etag, EntityTag::from_data_siphash13(&key, b"foo"));
+    assert_ne!(etag, EntityTag::from_data_siphash13(&[2u8; 16], b"foo"));
+    assert_ne!(etag, EntityTag::from_data_siphash24(&key, b"foo"));
+    assert_ne!(etag, EntityTag::from_data(b"foo"));
+
+    let mut hasher = siphasher::sip::SipHasher24::new_with_key(&key);
+    hasher.write(b"foo");
+
+    assert_eq!(Some(hasher.finish()), EntityTag::from_data_siphash24(&key, b"foo").as_hash());
+}
+
+#[test]
+fn precondition_trace() {
+    let current = EntityTag::with_str(false, "a").unwrap();
+
+    let list = EntityTagList::from_str("\"b\", W/\"a\"").unwrap();
+
+    let step = list.trace_if_match(Some(&current));
+
+    assert_eq!(PreconditionHeader::IfMatch, step.header());
+    assert_eq!(Comparison::Strong, step.comparison());
+    assert_eq!(None, step.matched());
+    assert_eq!(PreconditionFailure::NoneMatched, step.outcome().unwrap_err().failure());
+
+    let step = list.trace_if_none_match(Some(&current));
+
+    assert_eq!(Comparison::Weak, step.comparison());
+    assert_eq!(
+        Some(&EntityTagListItem::Tag(EntityTag::with_str(true, "a").unwrap())),
+        step.matched()
+    );
+    assert_eq!(
+        "If-None-Match: weak comparison, W/\"a\" matched, failed because an entity tag matches \
+         the current representation",
+        step.to_string()
+    );
+
+    let step = EntityTagList::Any.trace_if_match(Some(&current));
+
+    assert_eq!(Some(&EntityTagListItem::Any), step.matched());
+    assert!(step.outcome().is_ok());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_evaluate_preconditions_traced() {
+    use http::{HeaderMap, HeaderValue, Method};
+
+    let current = EntityTag::with_str(false, "a").unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(EntityTag::IF_MATCH_HEADER, HeaderValue::from_static("\"a\""));
+    headers.insert(EntityTag::IF_NONE_MATCH_HEADER, HeaderValue::from_static("W/\"a\""));
+
+    let (outcome, trace) =
+        http_header::evaluate_preconditions_traced(&Method::GET, &headers, Some(&current));
+
+    assert!(outcome.unwrap_err().is_not_modified());
+    assert_eq!(2, trace.steps().len());
+    assert!(trace.steps()[1].outcome().unwrap_err().is_not_modified());
+    assert_eq!(
+        "If-Match: strong comparison, \"a\" matched, passed; If-None-Match: weak comparison, \
+         W/\"a\" matched, failed because an entity tag matches the current representation",
+        trace.to_string()
+    );
+
+    headers.insert(EntityTag::IF_MATCH_HEADER, HeaderValue::from_static("a"));
+
+    let (outcome, trace) =
+        http_header::evaluate_preconditions_traced(&Method::GET, &headers, Some(&current));
+
+    assert_eq!(PreconditionHeader::IfMatch, outcome.unwrap_err().header());
+    assert_eq!(1, trace.steps().len());
+    assert!(trace.steps()[0].is_malformed());
+    assert_eq!(
+        outcome,
+        http_header::evaluate_preconditions(&Method::GET, &headers, Some(&current))
+    );
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn from_page() {
+    let collection = EntityTag::with_str(false, "v1").unwrap();
+
+    let etag = EntityTag::from_page(&collection, 2, 20, &[("sort", "name"), ("status", "active")]);
+
+    assert!(!etag.weak);
+    assert_eq!(
+        etag,
+        EntityTag::from_page(&collection, 2, 20, &[("status", "active"), ("sort", "name")])
+    );
+
+    assert_ne!(
+        etag,
+        EntityTag::from_page(&collection, 3, 20, &[("sort", "name"), ("status", "active")])
+    );
+    assert_ne!(
+        etag,
+        EntityTag::from_page(&collection, 2, 50, &[("sort", "name"), ("status", "active")])
+    );
+    assert_ne!(
+        etag,
+        EntityTag::from_page(&collection, 2, 20, &[("sort", "date"), ("status", "active")])
+    );
+    assert_ne!(
+        etag,
+        EntityTag::from_page(&EntityTag::with_str(false, "v2").unwrap(), 2, 20, &[
+            ("sort", "name"),
+            ("status", "active")
+        ])
+    );
+    assert_ne!(
+        EntityTag::from_page(&collection, 1, 20, &[("sort", "a"), ("sort", "b")]),
+        EntityTag::from_page(&collection, 1, 20, &[("sort", "b"), ("sort", "a")])
+    );
+    assert!(
+        EntityTag::from_page(&EntityTag::with_str(true, "v1").unwrap(), 2, 20, &[] as &[(
+            &str,
+            &str
+        )])
+        .weak
+    );
+}
+
+#[test]
+fn resource_version() {
+    use entity_tag::{ResourceVersion, RowVersionTag};
+
+    let mut version = ResourceVersion::new(41);
+
+    assert_eq!(ResourceVersion::new(42), version.bump());
+    assert_eq!(42, version.get());
+
+    let etag = version.to_entity_tag();
+
+    assert_eq!("\"2a\"", etag.to_string());
+    assert_eq!(Some(version), ResourceVersion::from_entity_tag(&etag));
+
+    assert_eq!(None, ResourceVersion::from_entity_tag(&EntityTag::with_str(true, "2a").unwrap()));
+    assert_eq!(None, ResourceVersion::from_entity_tag(&EntityTag::with_str(false, "2A").unwrap()));
+    assert_eq!(None, ResourceVersion::from_entity_tag(&EntityTag::with_str(false, "02a").unwrap()));
+    assert_eq!(None, ResourceVersion::from_entity_tag(&EntityTag::with_str(false, "").unwrap()));
+    assert_eq!(
+        Some(ResourceVersion::new(0)),
+        ResourceVersion::from_entity_tag(&EntityTag::with_str(false, "0").unwrap())
+    );
+
+    #[cfg(feature = "std")]
+    {
+        let mut version = ResourceVersion::new(u64::MAX - 1);
+
+        assert_eq!(u64::MAX, version.bump_to_now().get());
+
+        let mut version = ResourceVersion::new(0);
+
+        assert!(version.bump_to_now() > ResourceVersion::new(1));
+    }
+}
+
+#[test]
+fn prefix() {
+    let etag = EntityTag::with_str(true, "abc").unwrap();
+
+    let prefixed = etag.with_prefix("v2-").unwrap();
+
+    assert_eq!("W/\"v2-abc\"", prefixed.to_string());
+    assert_eq!(Some(etag.clone()), prefixed.strip_prefix("v2-"));
+    assert_eq!(None, prefixed.strip_prefix("v3-"));
+
+    assert_eq!(Err(EntityTagError::InvalidTag), etag.with_prefix("v 2"));
+    assert_eq!(Err(EntityTagError::InvalidTag), etag.with_prefix("\""));
+}
+
+#[test]
+fn try_map_tag() {
+    let etag = EntityTag::with_str(true, "abc").unwrap();
+
+    assert_eq!(
+        EntityTag::with_str(true, "ABC").unwrap(),
+        etag.try_map_tag(|tag| tag.to_uppercase()).unwrap()
+    );
+    assert_eq!(Err(EntityTagError::InvalidTag), etag.try_map_tag(|tag| format!("\"{}\"", tag)));
+    assert_eq!(Err(EntityTagError::InvalidTag), etag.try_map_tag(|tag| format!("{} x", tag)));
+}
+
+#[test]
+fn components() {
+    let etag = EntityTag::from_components(true, '-', ["1024", "abc"]).unwrap();
+
+    assert_eq!("W/\"1024-abc\"", etag.to_string());
+    assert_eq!(Some(["1024", "abc"]), etag.components::<2>('-'));
+    assert_eq!(None, etag.components::<3>('-'));
+    assert_eq!(None, etag.components::<1>('-'));
+    assert_eq!(Some(["1024-abc"]), etag.components::<1>(':'));
+
+    assert_eq!(
+        EntityTag::with_str(false, "1-2-3").unwrap(),
+        EntityTag::from_components(false, '-', 1..=3).unwrap()
+    );
+
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        EntityTag::from_components(false, '-', ["a-b", "c"])
+    );
+    assert_eq!(Err(EntityTagError::InvalidTag), EntityTag::from_components(false, ' ', ["a", "b"]));
+    assert_eq!(
+        Err(EntityTagError::InvalidTag),
+        EntityTag::from_components(false, '-', ["a\"", "b"])
+    );
+}
+
+#[test]
+fn matches_header_value() {
+    let strong = EntityTag::with_str(false, "abc").unwrap();
+    let weak = EntityTag::with_str(true, "abc").unwrap();
+
+    assert!(strong.matches_header_value("\"abc\"", Comparison::Strong));
+    assert!(strong.matches_header_value(" \"abc\"\t", Comparison::Strong));
+    assert!(!strong.matches_header_value("W/\"abc\"", Comparison::Strong));
+    assert!(strong.matches_header_value("W/\"abc\"", Comparison::Weak));
+    assert!(!weak.matches_header_value("\"abc\"", Comparison::Strong));
+    assert!(weak.matches_header_value("\"abc\"", Comparison::Weak));
+    assert!(!weak.matches_header_value("W/\"abd\"", Comparison::Weak));
+    assert!(!weak.matches_header_value("abc", Comparison::Weak));
+    assert!(!weak.matches_header_value("\"abc\", \"def\"", Comparison::Weak));
+}
+
+#[cfg(all(feature = "tide", feature = "generate"))]
+#[test]
+fn tide_middleware() {
+    use entity_tag::tide_middleware::{ETagMiddleware, RequestExt};
+    use futures::executor::block_on;
+    use tide::http::{Method, Request, Response, StatusCode, Url};
+
+    let mut app = tide::new();
+
+    app.with(ETagMiddleware::new());
+    app.at("/").get(|_| async { Ok("hello") });
+    app.at("/echo").get(|request: tide::Request<()>| async move {
+        Ok(request.if_none_match().map(|list| list.to_string()).unwrap_or_default())
+    });
+
+    let etag = EntityTag::from_data("hello");
+
+    let respond = |path: &str, if_none_match: Option<&str>| {
+        let mut request =
+            Request::new(Method::Get, Url::parse("http://localhost").unwrap().join(path).unwrap());
+
+        if let Some(if_none_match) = if_none_match {
+            request.insert_header("If-None-Match", if_none_match);
+        }
+
+        block_on(app.respond::<_, Response>(request)).unwrap()
+    };
+
+    let mut response = respond("/", None);
+    assert_eq!(StatusCode::Ok, response.status());
+    assert_eq!(etag.to_string(), response.header("ETag").unwrap().as_str());
+    assert_eq!("hello", block_on(response.body_string()).unwrap());
+
+    let response = respond("/", Some(&format!("\"x\", W/{}", etag)));
+    assert_eq!(StatusCode::NotModified, response.status());
+    assert_eq!(etag.to_string(), response.header("ETag").unwrap().as_str());
+
+    assert_eq!(StatusCode::Ok, respond("/", Some("\"x\"")).status());
+    assert_eq!(StatusCode::Ok, respond("/", Some("malformed")).status());
+
+    let mut response = respond("/echo", Some("\"a\",W/\"b\""));
+    assert_eq!("\"a\", W/\"b\"", block_on(response.body_string()).unwrap());
+}
+
+#[cfg(feature = "client-cache")]
+#[test]
+fn client_cache() {
+    use entity_tag::client_cache::*;
+
+    let url = "https://example.com/a";
+
+    let mut cache = ClientCache::new(MemoryStorage::new());
+
+    assert!(!cache.revalidation(url).is_conditional());
+    assert_eq!(Ok(None), cache.merge_not_modified(url, None, None));
+
+    let etag = EntityTag::with_str(false, "v1").unwrap();
+
+    cache.store(url, etag.clone(), Some("Sun, 06 Nov 1994 08:49:37 GMT"), b"hello").unwrap();
+
+    assert_eq!(&etag, cache.entry(url).unwrap().etag());
+    assert_eq!(
+        vec![
+            ("if-none-match", String::from("\"v1\"")),
+            ("if-modified-since", String::from("Sun, 06 Nov 1994 08:49:37 GMT")),
+        ],
+        cache.revalidation(url).to_headers()
+    );
+
+    assert_eq!(
+        Ok(Some(b"hello".to_vec())),
+        cache.merge_not_modified(url, Some(&etag), Some("Mon, 07 Nov 1994 08:49:37 GMT"))
+    );
+    assert_eq!(Some("Mon, 07 Nov 1994 08:49:37 GMT"), cache.entry(url).unwrap().last_modified());
+    assert_eq!(Ok(Some(b"hello".to_vec())), cache.merge_not_modified(url, None, None));
+    assert_eq!(
+        Ok(Some(b"hello".to_vec())),
+        cache.merge_not_modified(url, Some(&EntityTag::with_str(true, "v1").unwrap()), None)
+    );
+
+    // a different entity tag drops the entry
+    assert_eq!(
+        Ok(None),
+        cache.merge_not_modified(url, Some(&EntityTag::with_str(false, "v2").unwrap()), None)
+    );
+    assert!(cache.entry(url).is_none());
+    assert!(cache.storage().is_empty());
+
+    // a weak cached entity tag is not selected by a strong one
+    cache.store(url, EntityTag::with_str(true, "v3").unwrap(), None, b"world").unwrap();
+    assert_eq!(
+        Ok(None),
+        cache.merge_not_modified(url, Some(&EntityTag::with_str(false, "v3").unwrap()), None)
+    );
+}
+
+#[test]
+fn entity_tag_map() {
+    let mut map = EntityTagMap::new();
+
+    let upstream = EntityTag::with_str(false, "u1").unwrap();
+    let downstream = EntityTag::with_str(false, "d1").unwrap();
+
+    map.insert(upstream.clone(), downstream.clone());
+    map.insert(EntityTag::with_str(false, "u2").unwrap(), EntityTag::with_str(true, "d2").unwrap());
+
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&downstream), map.to_downstream(&upstream));
+    assert_eq!(Some(&upstream), map.to_upstream(&downstream));
+    assert_eq!(None, map.to_downstream(&downstream));
+
+    assert_eq!(
+        Some(EntityTagList::from_str("\"u1\", \"u2\"").unwrap()),
+        map.translate_if_none_match(&EntityTagList::from_str("W/\"d1\", \"x\", W/\"d2\"").unwrap())
+    );
+    assert_eq!(None, map.translate_if_none_match(&EntityTagList::from_str("\"x\"").unwrap()));
+    assert_eq!(
+        Some(EntityTagList::Any),
+        map.translate_if_none_match(&EntityTagList::from_str("*").unwrap())
+    );
+
+    assert_eq!(
+        Some(EntityTagList::from_str("\"u1\"").unwrap()),
+        map.translate_if_match(&EntityTagList::from_str("\"d1\", \"d2\", W/\"d1\"").unwrap())
+    );
+    assert_eq!(None, map.translate_if_match(&EntityTagList::from_str("W/\"d1\"").unwrap()));
+
+    // re-registering keeps the mapping one-to-one
+    map.insert(upstream.clone(), EntityTag::with_str(false, "d3").unwrap());
+    assert_eq!(2, map.len());
+    assert_eq!(None, map.to_upstream(&downstream));
+
+    assert_eq!(Some(EntityTag::with_str(false, "d3").unwrap()), map.remove_upstream(&upstream));
+    assert_eq!(1, map.len());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli() {
+    use std::process::Command;
+
+    let etag =
+        |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_etag")).args(args).output().unwrap();
+
+    let output = etag(&["file", "-f", "header", "Cargo.toml"]);
+    assert!(output.status.success());
+    assert_eq!(
+        format!(
+            "{}\n",
+            EntityTag::from_data(&std::fs::read("Cargo.toml").unwrap()).to_header_string()
+        ),
+        String::from_utf8(output.stdout).unwrap()
+    );
+
+    let output = etag(&["compare", "\"a\"", "W/\"a\""]);
+    assert_eq!(
+        "strong comparison: false\nweak comparison: true\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+
+    assert!(etag(&["check", "W/\"a\""]).status.success());
+    assert!(!etag(&["check", "a"]).status.success());
+}
+
+#[test]
+fn from_validated() {
+    let tag = ValidatedTag::new("\"abc\"").unwrap();
+
+    assert_eq!("abc", tag.as_str());
+    assert_eq!("\"abc\"", tag.to_string());
+    assert_eq!(tag, ValidatedTag::new("abc").unwrap());
+    assert_eq!(tag, ValidatedTag::with_string(String::from("\"abc\"")).unwrap());
+    assert_eq!(Err(EntityTagError::InvalidTag), ValidatedTag::new("a c"));
+    assert_eq!(Err(EntityTagError::MissingClosingDoubleQuote), ValidatedTag::new("\"abc"));
+
+    assert_eq!(
+        EntityTag::with_str(true, "abc").unwrap(),
+        EntityTag::from_validated(true, tag.clone())
+    );
+
+    let etag = EntityTag::from_str("W/\"def\"").unwrap();
+
+    assert_eq!(
+        EntityTag::with_str(false, "def").unwrap(),
+        EntityTag::from_validated(false, ValidatedTag::from(etag).into_owned())
+    );
+    assert_eq!(tag, ValidatedTag::from(EntityTagRef::from_str("\"abc\"").unwrap()));
+}
+
+#[test]
+fn entity_tag_interner() {
+    use std::sync::Arc;
+
+    let mut interner = EntityTagInterner::new();
+
+    let a = interner.intern(&EntityTag::from_str("\"a\"").unwrap());
+    let b = interner.intern(&EntityTag::with_string(false, String::from("a")).unwrap());
+    let weak = interner.intern(&EntityTag::from_str("W/\"a\"").unwrap());
+
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(!Arc::ptr_eq(&a, &weak));
+    assert!(weak.weak);
+    assert_eq!(2, interner.len());
+
+    assert!(interner.get(&EntityTag::from_str("\"b\"").unwrap()).is_none());
+    assert!(Arc::ptr_eq(&weak, &interner.get(&EntityTag::from_str("W/\"a\"").unwrap()).unwrap()));
+
+    drop(weak);
+    interner.remove_unused();
+    assert_eq!(1, interner.len());
+    assert!(interner.get(&EntityTag::from_str("W/\"a\"").unwrap()).is_none());
+
+    interner.clear();
+    assert!(interner.is_empty());
+    assert_eq!("\"a\"", a.to_string());
+}
+
+#[test]
+fn shared_entity_tag() {
+    let shared: SharedEntityTag = "W/\"abc\"".parse().unwrap();
+    let clone = shared.clone();
+
+    assert_eq!(shared, clone);
+    assert!(std::ptr::eq(shared.get_tag(), clone.get_tag()));
+    assert_eq!("W/\"abc\"", clone.to_string());
+    assert_eq!(EntityTag::with_str(true, "abc").unwrap(), shared.as_entity_tag());
+    assert_eq!(EntityTag::with_str(true, "abc").unwrap(), EntityTag::from(shared.clone()));
+
+    let strong = EntityTag::with_str(false, "abc").unwrap();
+    assert!(shared.weak_eq(&strong));
+    assert!(!shared.strong_eq(&strong));
+    assert!(SharedEntityTag::from(&strong).strong_eq(&strong));
+    assert_eq!(
+        SharedEntityTag::from(strong.clone()),
+        SharedEntityTag::with_str(false, "\"abc\"").unwrap()
+    );
+
+    assert_eq!(Err(EntityTagError::InvalidTag), SharedEntityTag::with_str(false, "a c"));
+    assert_eq!(Err(EntityTagError::MissingStartingDoubleQuote), "abc".parse::<SharedEntityTag>());
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn tag_encoding() {
+    let hash = 0xFBFF_0000_0000_0000u64;
+
+    assert_eq!("AAAAAAAA//s", EntityTag::from_hash(false, hash).get_tag());
+
+    let url_safe = TagEncoding::new().url_safe(true);
+    let padded = TagEncoding::new().padding(true);
+
+    let etag = EntityTag::from_hash_with_encoding(false, hash, url_safe);
+    assert_eq!("AAAAAAAA__s", etag.get_tag());
+    assert_eq!(Some(hash), etag.as_hash_with_encoding(url_safe));
+    assert_eq!(None, etag.as_hash());
+
+    let etag = EntityTag::from_hash_with_encoding(true, hash, padded);
+    assert_eq!("AAAAAAAA//s=", etag.get_tag());
+    assert_eq!(Some(hash), etag.as_hash_with_encoding(padded));
+    assert_eq!(None, etag.as_hash());
+
+    assert_eq!(
+        EntityTag::from_data("hello"),
+        EntityTag::from_data_with_encoding("hello", TagEncoding::new())
+    );
+    assert_eq!(
+        EntityTag::from_data("hello").as_hash(),
+        EntityTag::from_data_with_encoding("hello", url_safe.padding(true))
+            .as_hash_with_encoding(url_safe.padding(true))
+    );
+
+    let mut hasher = EntityTagHasher::new();
+    hasher.write(b"hello");
+    assert_eq!(
+        EntityTag::from_data_with_encoding("hello", padded),
+        hasher.finish_with_encoding(padded)
+    );
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn tag_encoding_truncate() {
+    use std::num::NonZeroUsize;
+
+    let hash = 0xFBFF_0000_0000_0000u64;
+
+    let encoding = TagEncoding::new().truncate(NonZeroUsize::new(6).unwrap());
+
+    let etag = EntityTag::from_hash_with_encoding(false, hash, encoding);
+    assert_eq!("AAAAAA", etag.get_tag());
+    assert_eq!(None, etag.as_hash_with_encoding(encoding));
+
+    assert_eq!(
+        &EntityTag::from_data("hello").get_tag()[..8],
+        EntityTag::from_data_with_encoding(
+            "hello",
+            TagEncoding::new().truncate(NonZeroUsize::new(8).unwrap())
+        )
+        .get_tag()
+    );
+
+    let encoding = TagEncoding::new().truncate(NonZeroUsize::new(1).unwrap());
+    assert_eq!("A", EntityTag::from_hash_with_encoding(false, hash, encoding).get_tag());
+
+    let encoding = TagEncoding::new().truncate(NonZeroUsize::new(20).unwrap());
+    assert_eq!(
+        EntityTag::from_hash(true, hash),
+        EntityTag::from_hash_with_encoding(true, hash, encoding)
+    );
+    assert_eq!(Some(hash), EntityTag::from_hash(true, hash).as_hash_with_encoding(encoding));
+}
+
+#[test]
+fn alternate_display() {
+    let etag = EntityTag::with_str(true, "a\\b\u{202e}c\u{85}").unwrap();
+
+    assert_eq!("W/\"a\\b\u{202e}c\u{85}\"", format!("{}", etag));
+    assert_eq!("W/\"a\\\\b\\u{202e}c\\u{85}\"", format!("{:#}", etag));
+    assert_eq!("W/\"a\\\\b\\u{202e}c\\u{85}\"  ", format!("{:#25}", etag));
+    assert_eq!("\"abc\"", format!("{:#}", EntityTag::with_str(false, "abc").unwrap()));
+    assert_eq!(
+        format!("{:#}", etag),
+        format!("{:#}", EntityTagRef::from_str("W/\"a\\b\u{202e}c\u{85}\"").unwrap())
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use entity_tag::serde_forms;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Resource {
+        etag:     EntityTag<'static>,
+        #[serde(with = "serde_forms::structure")]
+        current:  EntityTag<'static>,
+        #[serde(with = "serde_forms::structure_option")]
+        previous: Option<EntityTag<'static>>,
+        #[serde(with = "serde_forms::nullable")]
+        upstream: Option<EntityTag<'static>>,
+    }
+
+    let resource = Resource {
+        etag:     EntityTag::with_str(true, "a").unwrap(),
+        current:  EntityTag::with_str(false, "b").unwrap(),
+        previous: None,
+        upstream: Some(EntityTag::with_str(false, "c").unwrap()),
+    };
+
+    let json = serde_json::to_string(&resource).unwrap();
+    assert_eq!(
+        r#"{"etag":"W/\"a\"","current":{"weak":false,"tag":"b"},"previous":null,"upstream":"\"c\""}"#,
+        json
+    );
+    assert_eq!(resource, serde_json::from_str(&json).unwrap());
+
+    let resource: Resource = serde_json::from_str(
+        r#"{"etag":"\"a\"","current":{"tag":"b","weak":true},"previous":{"weak":false,"tag":"c"},"upstream":""}"#,
+    )
+    .unwrap();
+    assert_eq!(EntityTag::with_str(true, "b").unwrap(), resource.current);
+    assert_eq!(Some(EntityTag::with_str(false, "c").unwrap()), resource.previous);
+    assert_eq!(None, resource.upstream);
+
+    assert!(serde_json::from_str::<EntityTag>(r#""a""#).is_err());
+    assert!(serde_json::from_str::<Resource>(
+        r#"{"etag":"\"a\"","current":{"weak":false,"tag":"b c"},"previous":null,"upstream":null}"#
+    )
+    .is_err());
+
+    #[cfg(feature = "serde_with")]
+    {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Versions {
+            #[serde(with = "serde_with::As::<Vec<serde_forms::Structure>>")]
+            etags: Vec<EntityTag<'static>>,
+        }
+
+        let versions = Versions {
+            etags: vec![EntityTag::with_str(true, "a").unwrap()]
+        };
+
+        let json = serde_json::to_string(&versions).unwrap();
+        assert_eq!(r#"{"etags":[{"weak":true,"tag":"a"}]}"#, json);
+        assert_eq!(versions, serde_json::from_str(&json).unwrap());
+    }
+}
+
+#[cfg(all(feature = "metrics", feature = "generate"))]
+#[test]
+fn metrics() {
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    #[derive(Default)]
+    struct Value(AtomicU64);
+
+    impl CounterFn for Value {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+
+        fn absolute(&self, value: u64) {
+            self.0.fetch_max(value, Ordering::Relaxed);
+        }
+    }
+
+    #[derive(Default)]
+    struct TestRecorder(Mutex<BTreeMap<String, Arc<Value>>>);
+
+    impl TestRecorder {
+        fn get(&self, name: &str) -> u64 {
+            self.0.lock().unwrap().get(name).map_or(0, |value| value.0.load(Ordering::Relaxed))
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counters = self.0.lock().unwrap();
+
+            Counter::from_arc(counters.entry(key.name().to_string()).or_default().clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    let recorder = TestRecorder::default();
+
+    metrics::with_local_recorder(&recorder, || {
+        EntityTag::from_data(b"foo");
+
+        let mut hasher = EntityTagHasher::new();
+        hasher.write(b"hello");
+        hasher.write(b"world");
+    });
+
+    assert_eq!(13, recorder.get("entity_tag_hashed_bytes_total"));
+
+    #[cfg(feature = "http")]
+    {
+        use http::{HeaderMap, HeaderValue, Method};
+
+        let etag = EntityTag::with_str(false, "a").unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(EntityTag::IF_NONE_MATCH_HEADER, HeaderValue::from_static("\"a\""));
+
+        metrics::with_local_recorder(&recorder, || {
+            assert!(
+                http_header::evaluate_preconditions(&Method::GET, &headers, Some(&etag)).is_err()
+            );
+            assert!(
+                http_header::evaluate_preconditions(&Method::PUT, &headers, Some(&etag)).is_err()
+            );
+            assert!(http_header::evaluate_preconditions(
+                &Method::PUT,
+                &HeaderMap::new(),
+                Some(&etag)
+            )
+            .is_ok());
+        });
+
+        assert_eq!(1, recorder.get("entity_tag_not_modified_total"));
+        assert_eq!(1, recorder.get("entity_tag_precondition_failed_total"));
+    }
+}
+
+#[test]
+fn entity_tag_store() {
+    use std::num::NonZeroUsize;
+
+    let a = EntityTag::with_str(false, "a").unwrap();
+    let b = EntityTag::with_str(true, "b").unwrap();
+    let c = EntityTag::with_str(false, "c").unwrap();
+
+    let mut store = EntityTagStore::new();
+    assert_eq!(None, store.capacity());
+
+    assert_eq!(None, store.insert("/a", a.clone()));
+    assert_eq!(Some(a.clone()), store.insert("/a", b.clone()));
+    assert_eq!(Some(&b), store.get("/a"));
+    assert_eq!(Some(b.clone()), store.remove("/a"));
+    assert!(store.is_empty());
+
+    let mut store = EntityTagStore::bounded(NonZeroUsize::new(2).unwrap());
+    assert_eq!(2, store.capacity().unwrap().get());
+
+    store.insert("/a", a.clone());
+    store.insert("/b", b.clone());
+
+    // "/a" becomes the most recently used entry, so "/b" is evicted
+    assert_eq!(Some(&a), store.get("/a"));
+    store.insert("/c", c.clone());
+    assert_eq!(2, store.len());
+    assert!(!store.contains_key("/b"));
+
+    // peeking does not change the order of eviction
+    assert_eq!(Some(&a), store.peek("/a"));
+    store.insert("/b", b.clone());
+    assert_eq!(None, store.peek("/a"));
+    assert_eq!(Some(&c), store.peek("/c"));
+    assert_eq!(Some(&b), store.peek("/b"));
+
+    // replacing the entity tag of a key does not evict anything
+    assert_eq!(Some(c), store.insert("/c", a.clone()));
+    assert_eq!(2, store.len());
+
+    assert_eq!(Some(b), store.remove("/b"));
+    store.insert("/d", a.clone());
+    assert_eq!(2, store.len());
+    assert!(store.contains_key("/c"));
+
+    store.clear();
+    assert!(store.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn entity_tag_store_save() {
+    use std::{fs, num::NonZeroUsize};
+
+    let a = EntityTag::with_str(false, "a").unwrap();
+    let b = EntityTag::with_str(true, "b").unwrap();
+
+    let mut store = EntityTagStore::new();
+    store.insert("/b", b.clone());
+    store.insert("/a", a.clone());
+
+    assert_eq!(b"ETS\x01\x02/a\x00\x01a\x02/b\x01\x01b", store.to_bytes().as_slice());
+
+    let path = std::env::temp_dir().join(format!("entity-tag-store-{}", std::process::id()));
+
+    store.save(&path).unwrap();
+
+    let mut loaded = EntityTagStore::load(&path).unwrap();
+    assert_eq!(2, loaded.len());
+    assert_eq!(Some(&a), loaded.get("/a"));
+    assert_eq!(Some(&b), loaded.get("/b"));
+
+    // a bounded store keeps the order of eviction
+    let mut bounded = EntityTagStore::bounded(NonZeroUsize::new(2).unwrap());
+    bounded.insert("/a", a.clone());
+    bounded.insert("/b", b.clone());
+    bounded.get("/a");
+
+    let mut loaded = EntityTagStore::bounded(NonZeroUsize::new(2).unwrap());
+    loaded.extend_from_bytes(&bounded.to_bytes()).unwrap();
+    loaded.insert("/c", a.clone());
+    assert!(loaded.contains_key("/a"));
+    assert!(!loaded.contains_key("/b"));
+
+    // nothing is inserted from malformed data
+    let mut store = EntityTagStore::new();
+    assert_eq!(Err(BinaryDecodeError::InvalidHeader), store.extend_from_bytes(b"ETS\x02"));
+    assert_eq!(
+        Err(BinaryDecodeError::UnexpectedEnd),
+        store.extend_from_bytes(b"ETS\x01\x02/a\x00\x01a\x02/b")
+    );
+    assert_eq!(
+        Err(BinaryDecodeError::InvalidKey),
+        store.extend_from_bytes(b"ETS\x01\x01\xff\x00\x01a")
+    );
+    assert!(store.is_empty());
+
+    fs::write(&path, b"malformed").unwrap();
+
+    assert_eq!(std::io::ErrorKind::InvalidData, EntityTagStore::load(&path).unwrap_err().kind());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn tag_storage() {
+    use std::fmt::Debug;
+
+    fn exercise<S: TagStorage>(storage: &mut S)
+    where
+        S::Error: Debug, {
+        let a = EntityTag::with_str(false, "a").unwrap();
+        let b = EntityTag::with_str(true, "b").unwrap();
+
+        assert_eq!(None, storage.get_tag("/a").unwrap());
+
+        storage.set_tag("/a", &a).unwrap();
+        assert_eq!(Some(a), storage.get_tag("/a").unwrap());
+
+        storage.set_tag("/a", &b).unwrap();
+        assert_eq!(Some(b), storage.get_tag("/a").unwrap());
+
+        storage.remove_tag("/a").unwrap();
+        assert_eq!(None, storage.get_tag("/a").unwrap());
+    }
+
+    exercise(&mut EntityTagStore::new());
+
+    #[cfg(feature = "sled")]
+    {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let mut tree = db.open_tree("etags").unwrap();
+
+        exercise(&mut tree);
+
+        // a malformed value is treated as absent
+        tree.insert("/b", b"\x02\x00").unwrap();
+        assert_eq!(None, tree.get_tag("/b").unwrap());
+    }
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn redis_tag_storage() {
+    use redis::Value;
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    let etag = EntityTag::with_str(true, "a").unwrap();
+
+    let connection = MockRedisConnection::new(vec![
+        MockCmd::new(redis::cmd("SET").arg("etag:/a").arg(b"\x01\x01a"), Ok(Value::Okay)),
+        MockCmd::new(redis::cmd("GET").arg("etag:/a"), Ok(b"\x01\x01a".to_vec())),
+        MockCmd::new(redis::cmd("DEL").arg("etag:/a"), Ok(Value::Int(1))),
+        MockCmd::new(redis::cmd("GET").arg("etag:/a"), Ok(Value::Nil)),
+        MockCmd::new(redis::cmd("GET").arg("tags/b"), Ok(b"malformed".to_vec())),
+    ]);
+
+    let mut storage = RedisTagStorage::new(connection);
+    assert_eq!("etag:", storage.prefix());
+
+    futures::executor::block_on(async {
+        storage.set_tag("/a", &etag).await.unwrap();
+        assert_eq!(Some(etag), storage.get_tag("/a").await.unwrap());
+
+        storage.remove_tag("/a").await.unwrap();
+        assert_eq!(None, storage.get_tag("/a").await.unwrap());
+    });
+
+    let connection = storage.connection_mut().clone();
+
+    let mut storage = RedisTagStorage::with_prefix(connection, "tags");
+
+    futures::executor::block_on(async {
+        assert_eq!(None, storage.get_tag("/b").await.unwrap());
+    });
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn file_tag_cache() {
+    use std::{
+        fs,
+        sync::{Arc, Barrier},
+        thread,
+        time::Duration,
+    };
+
+    let path = std::env::temp_dir().join(format!("entity-tag-file-cache-{}", std::process::id()));
+
+    fs::write(&path, vec![b'a'; 1 << 20]).unwrap();
+
+    let cache = Arc::new(FileTagCache::new());
+    let barrier = Arc::new(Barrier::new(8));
+
+    let handles = (0..8)
+        .map(|_| {
+            let cache = cache.clone();
+            let barrier = barrier.clone();
+            let path = path.clone();
+
+            thread::spawn(move || {
+                barrier.wait();
+
+                cache.get(&path).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let expected = EntityTag::from_data(&vec![b'a'; 1 << 20]);
+
+    for handle in handles {
+        assert_eq!(expected, handle.join().unwrap());
+    }
+
+    assert_eq!(1, cache.len());
+
+    // a modified file is hashed again
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&path, b"foo").unwrap();
+
+    assert_eq!(EntityTag::from_data(b"foo"), cache.get(&path).unwrap());
+    assert_eq!(1, cache.len());
+
+    cache.remove(&path);
+    assert!(cache.is_empty());
+
+    fs::remove_file(&path).unwrap();
+
+    assert!(cache.get(&path).is_err());
+}